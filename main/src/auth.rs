@@ -1,57 +1,166 @@
-use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
 
-use axum::async_trait;
-use axum_login::{AuthUser, AuthnBackend, UserId};
+use axum::{async_trait, body::Body, http::Request, middleware::Next, response::Response};
+use axum_login::{AuthSession, AuthUser, AuthnBackend, UserId};
+use tokio::task;
+use users_service::{
+    models,
+    ports::user_repository::{RepositoryFailure, UserRepository},
+};
+use web_htmx::{context::CurrentUser, resources::auth::Credentials};
 
 /**
-* This is the in memory implementation of the AuthnBackend trait from auth-login
-* https://docs.rs/axum-login/latest/axum_login/
+* The AuthnBackend from axum-login (https://docs.rs/axum-login/latest/axum_login/), users come
+* from a `UserRepository`: in memory with fixtures in tests, a database in production.
 *
-* Swap this out with a real implementation at your leisure!
+* Passwords are hashed (and verified) with password-auth.
 */
 
-#[derive(Debug, Clone)]
-pub struct User {
-    id: i64,
-    pw_hash: Vec<u8>,
-}
+#[derive(Clone, Debug)]
+pub struct User(models::User);
 
 impl AuthUser for User {
-    type Id = i64;
+    type Id = String;
 
     fn id(&self) -> Self::Id {
-        self.id
+        self.0.id.clone()
     }
 
+    // Changing the password signs the user out everywhere else.
     fn session_auth_hash(&self) -> &[u8] {
-        &self.pw_hash
+        self.0.password_hash.as_bytes()
+    }
+}
+
+pub struct Backend<R: UserRepository + ?Sized> {
+    users: Arc<R>,
+}
+
+impl<R: UserRepository + ?Sized> Backend<R> {
+    pub fn new(users: Arc<R>) -> Self {
+        Self { users }
     }
 }
 
-#[derive(Clone, Default)]
-pub struct Backend {
-    users: HashMap<i64, User>,
+// Not derived, `R` doesn't need to be `Clone`.
+impl<R: UserRepository + ?Sized> Clone for Backend<R> {
+    fn clone(&self) -> Self {
+        Self {
+            users: self.users.clone(),
+        }
+    }
 }
 
-#[derive(Clone)]
-pub struct Credentials {
-    user_id: i64,
+#[derive(Debug, thiserror::Error)]
+pub enum BackendError {
+    #[error(transparent)]
+    Repository(#[from] RepositoryFailure),
+    #[error(transparent)]
+    TaskJoin(#[from] task::JoinError),
 }
 
 #[async_trait]
-impl AuthnBackend for Backend {
+impl<R: UserRepository + ?Sized> AuthnBackend for Backend<R> {
     type User = User;
     type Credentials = Credentials;
-    type Error = std::convert::Infallible;
+    type Error = BackendError;
 
     async fn authenticate(
         &self,
-        Credentials { user_id }: Self::Credentials,
+        Credentials {
+            username, password, ..
+        }: Self::Credentials,
     ) -> Result<Option<Self::User>, Self::Error> {
-        Ok(self.users.get(&user_id).cloned())
+        let user = self.users.get_user_by_username(username).await?;
+
+        // Hashing is slow on purpose, keep it off the async runtime. Unknown usernames are checked
+        // against a stand-in, so they take as long as wrong passwords and can't be told apart.
+        let user = task::spawn_blocking(move || match user {
+            Some(user) => user.verify_password(&password).then_some(user),
+            None => {
+                unknown_user().verify_password(&password);
+                None
+            }
+        })
+        .await?;
+
+        Ok(user.map(User))
     }
 
     async fn get_user(&self, user_id: &UserId<Self>) -> Result<Option<Self::User>, Self::Error> {
-        Ok(self.users.get(user_id).cloned())
+        Ok(self.users.get_user(user_id.clone()).await?.map(User))
+    }
+}
+
+fn unknown_user() -> &'static models::User {
+    static UNKNOWN_USER: OnceLock<models::User> = OnceLock::new();
+
+    UNKNOWN_USER.get_or_init(|| models::User::new("", &rand::random::<u128>().to_string()))
+}
+
+/**
+* Hands the signed in user to web-htmx (as `CurrentUser`), which doesn't know about this backend.
+*/
+pub async fn provide_current_user<R: UserRepository + ?Sized>(
+    auth_session: AuthSession<Backend<R>>,
+    mut request: Request<Body>,
+    next: Next,
+) -> Response {
+    if let Some(user) = auth_session.user {
+        request
+            .extensions_mut()
+            .insert(CurrentUser { id: user.id() });
+    }
+
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use in_memory_user_repository::InMemoryUserRepository;
+
+    fn credentials(username: &str, password: &str) -> Credentials {
+        Credentials {
+            username: username.into(),
+            password: password.into(),
+            next: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_authenticate_checks_the_password() {
+        let ann = models::User::new("ann", "correct horse");
+        let backend = Backend::new(Arc::new(InMemoryUserRepository::with(vec![ann.clone()])));
+
+        let user = backend
+            .authenticate(credentials("ann", "correct horse"))
+            .await
+            .unwrap();
+        assert_eq!(user.map(|user| user.id()), Some(ann.id.clone()));
+
+        let user = backend
+            .authenticate(credentials("ann", "battery staple"))
+            .await
+            .unwrap();
+        assert!(user.is_none());
+
+        let user = backend
+            .authenticate(credentials("bob", "correct horse"))
+            .await
+            .unwrap();
+        assert!(user.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_get_user() {
+        let ann = models::User::new("ann", "correct horse");
+        let users: Arc<dyn UserRepository> =
+            Arc::new(InMemoryUserRepository::with(vec![ann.clone()]));
+        let backend = Backend::new(users);
+
+        let user = backend.get_user(&ann.id).await.unwrap();
+
+        assert_eq!(user.map(|user| user.0), Some(ann));
     }
 }
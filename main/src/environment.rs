@@ -1,25 +1,525 @@
-use std::env;
+use std::{
+    collections::HashMap,
+    env, fmt, fs,
+    net::SocketAddr,
+    num::NonZeroU32,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use dotenvy::dotenv;
 use tracing::instrument;
 
+use crate::{sessions::SessionStoreConfig, users::UserStoreConfig};
+
+// Read when it exists, `CONFIG_FILE` points to another file.
+const DEFAULT_CONFIG_FILE: &str = "config.toml";
+
 /**
-* A single struct that represents all of the env vars.
+* A single struct that represents all of the configuration.
 * This struct should be created once during bootstrapping and then its values can be handed out as
-* necessary
+* necessary.
+*
+* Each value comes from (later wins): its default, the TOML config file
+* (`session_expiry_minutes = 30`), `.env`, then the environment (`SESSION_EXPIRY_MINUTES=30`).
 */
 pub struct Environment {
-    // Example:
-    // pub mongo_db_url: String,
+    // `BIND_ADDRESS`, defaults to 0.0.0.0:3000.
+    pub bind_address: SocketAddr,
+
+    // `SESSION_EXPIRY_MINUTES`: sessions end after this long without a request, defaults to 60.
+    pub session_expiry_minutes: NonZeroU32,
+
+    // `SESSION_STORE`: memory (default), mongodb, postgres, sqlite or file. `SESSION_STORE_URL` is
+    // the database url, or the directory of the file store (defaults to `.sessions`).
+    pub session_store: SessionStoreConfig,
+
+    // `SESSION_CLEANUP_MINUTES`: how often expired sessions are deleted, defaults to 10.
+    pub session_cleanup_minutes: NonZeroU32,
+
+    // `SESSION_SECURE_COOKIES`: only send the session cookie over https, defaults to false.
+    pub session_secure_cookies: bool,
+
+    // `FLASH_KEY`: signs flash messages, base64 encoded (64 bytes or more). Required in release
+    // builds, see `dev_flash_key` for development.
+    pub flash_key: Option<axum_flash::Key>,
+
+    // `FLASH_PREVIOUS_KEYS`: comma separated keys `FLASH_KEY` replaced. Flashes signed with them
+    // are still accepted, so the key can be rotated without losing any.
+    pub flash_previous_keys: Vec<axum_flash::Key>,
+
+    // `FLASH_KEY_FILE`: where `dev_flash_key` keeps its key, defaults to `.flash-key`.
+    pub flash_key_file: PathBuf,
+
+    // `USER_STORE`: memory (default), postgres or mongodb. `USER_STORE_URL` is the database url.
+    pub user_store: UserStoreConfig,
+
+    // `NOTIFICATIONS_MONGO_DB_URL`: notifications are kept in memory when this isn't set.
+    pub notifications_mongo_db_url: Option<String>,
+
+    // `NOTIFICATIONS_MONGO_DB_NAME`: the database they're kept in, defaults to `notifications`.
+    pub notifications_mongo_db_name: String,
 }
 
+const KEYS: &[&str] = &[
+    "bind_address",
+    "session_expiry_minutes",
+    "session_store",
+    "session_store_url",
+    "session_cleanup_minutes",
+    "session_secure_cookies",
+    "flash_key",
+    "flash_previous_keys",
+    "flash_key_file",
+    "user_store",
+    "user_store_url",
+    "notifications_mongo_db_url",
+    "notifications_mongo_db_name",
+];
+
 /**
-* Function to do all the "dirty work" of pulling env vars into the Environment struct.
+* Every problem found while loading the configuration, so they can be fixed in one go.
 */
-#[instrument]
-pub fn load_environment() -> Environment {
+#[derive(Debug, PartialEq)]
+pub struct EnvironmentError(pub Vec<String>);
+
+impl fmt::Display for EnvironmentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Invalid configuration:")?;
+        for problem in &self.0 {
+            writeln!(f, "  - {}", problem)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for EnvironmentError {}
+
+/**
+* Adds the variables from `.env` to the environment. Call it before `init_tracing`, `.env` can set
+* `RUST_LOG` and `LOG_FORMAT`.
+*/
+pub fn load_dotenv() {
+    // Doesn't override variables that are already set, so the environment wins over `.env`.
     dotenv().ok();
-    Environment {
-        // mongo_db_rl: env::var("MONGO_DB_URL").expect("MONGO_DB_URL must be set"),
+}
+
+/**
+* Function to do all the "dirty work" of pulling the config file and env vars into the Environment
+* struct. `.env` is already loaded (see `load_dotenv`).
+*/
+#[instrument]
+pub fn load_environment() -> Result<Environment, EnvironmentError> {
+    let env_vars: HashMap<String, String> = env::vars().collect();
+
+    let config_file = match env_vars.get("CONFIG_FILE") {
+        Some(path) => Some(fs::read_to_string(path).map_err(|e| {
+            EnvironmentError(vec![format!("CONFIG_FILE: unable to read {}: {}", path, e)])
+        })?),
+        None => fs::read_to_string(DEFAULT_CONFIG_FILE).ok(),
+    };
+
+    Environment::from_sources(config_file.as_deref(), &env_vars)
+}
+
+impl Environment {
+    pub fn from_sources(
+        config_file: Option<&str>,
+        env_vars: &HashMap<String, String>,
+    ) -> Result<Self, EnvironmentError> {
+        let mut config = Config::default();
+
+        if let Some(config_file) = config_file {
+            config.merge_toml(config_file);
+        }
+        config.merge_env(env_vars);
+
+        let environment = Environment {
+            bind_address: config
+                .parse("bind_address")
+                .unwrap_or(SocketAddr::from(([0, 0, 0, 0], 3000))),
+            session_expiry_minutes: config
+                .parse("session_expiry_minutes")
+                .unwrap_or(NonZeroU32::new(60).unwrap()),
+            session_store: session_store(&mut config),
+            session_cleanup_minutes: config
+                .parse("session_cleanup_minutes")
+                .unwrap_or(NonZeroU32::new(10).unwrap()),
+            session_secure_cookies: config.parse("session_secure_cookies").unwrap_or(false),
+            flash_key: match cfg!(debug_assertions) {
+                true => config.parse_with("flash_key", parse_flash_key),
+                false => config.required("flash_key", parse_flash_key),
+            },
+            flash_previous_keys: config
+                .parse_with("flash_previous_keys", parse_flash_keys)
+                .unwrap_or_default(),
+            flash_key_file: config
+                .parse("flash_key_file")
+                .unwrap_or(PathBuf::from(".flash-key")),
+            user_store: user_store(&mut config),
+            notifications_mongo_db_url: config.parse_with("notifications_mongo_db_url", |value| {
+                parse_database_url(value, MONGO_DB_SCHEMES)
+            }),
+            notifications_mongo_db_name: config
+                .parse("notifications_mongo_db_name")
+                .unwrap_or("notifications".to_string()),
+        };
+
+        match config.problems.is_empty() {
+            true => Ok(environment),
+            false => Err(EnvironmentError(config.problems)),
+        }
+    }
+}
+
+// Raw values by key, and the problems found so far.
+#[derive(Default)]
+struct Config {
+    values: HashMap<&'static str, String>,
+    problems: Vec<String>,
+}
+
+impl Config {
+    fn merge_toml(&mut self, config_file: &str) {
+        let table = match config_file.parse::<toml::Table>() {
+            Ok(table) => table,
+            Err(e) => return self.problems.push(format!("config file: {}", e.message())),
+        };
+
+        for (key, value) in table {
+            let Some(key) = KEYS.iter().find(|k| **k == key) else {
+                self.problems
+                    .push(format!("config file: unknown key `{}`", key));
+                continue;
+            };
+
+            let value = match value {
+                toml::Value::String(value) => value,
+                toml::Value::Integer(value) => value.to_string(),
+                toml::Value::Boolean(value) => value.to_string(),
+                _ => {
+                    self.problems
+                        .push(format!("{}: expected a string, number or boolean", key));
+                    continue;
+                }
+            };
+
+            self.values.insert(key, value);
+        }
+    }
+
+    fn merge_env(&mut self, env_vars: &HashMap<String, String>) {
+        for key in KEYS {
+            if let Some(value) = env_vars.get(&key.to_uppercase()) {
+                self.values.insert(key, value.clone());
+            }
+        }
+    }
+
+    fn parse<T: FromStr>(&mut self, key: &str) -> Option<T>
+    where
+        T::Err: fmt::Display,
+    {
+        self.parse_with(key, |value| value.parse::<T>().map_err(|e| e.to_string()))
+    }
+
+    fn parse_with<T>(&mut self, key: &str, parse: impl Fn(&str) -> Result<T, String>) -> Option<T> {
+        let value = self.values.get(key)?;
+
+        match parse(value) {
+            Ok(value) => Some(value),
+            Err(e) => {
+                self.problems.push(format!(
+                    "{} ({}): invalid value {:?}, {}",
+                    key.to_uppercase(),
+                    key,
+                    value,
+                    e
+                ));
+                None
+            }
+        }
+    }
+
+    fn required<T>(&mut self, key: &str, parse: impl Fn(&str) -> Result<T, String>) -> Option<T> {
+        if !self.values.contains_key(key) {
+            self.problems
+                .push(format!("{} ({}): missing", key.to_uppercase(), key));
+        }
+
+        self.parse_with(key, parse)
+    }
+}
+
+// Falls back to memory when `session_store` is invalid, the problem is reported.
+fn session_store(config: &mut Config) -> SessionStoreConfig {
+    let kind = config.parse_with("session_store", |value| match value {
+        "memory" | "mongodb" | "postgres" | "sqlite" | "file" => Ok(value.to_string()),
+        _ => Err("expected memory, mongodb, postgres, sqlite or file".into()),
+    });
+
+    let store = match kind.as_deref() {
+        Some("mongodb") => database_url(config, "session_store_url", MONGO_DB_SCHEMES)
+            .map(|url| SessionStoreConfig::MongoDb { url }),
+        Some("postgres") => database_url(config, "session_store_url", POSTGRES_SCHEMES)
+            .map(|url| SessionStoreConfig::Postgres { url }),
+        Some("sqlite") => database_url(config, "session_store_url", &["sqlite:"])
+            .map(|url| SessionStoreConfig::Sqlite { url }),
+        Some("file") => Some(SessionStoreConfig::File {
+            dir: config
+                .parse("session_store_url")
+                .unwrap_or(PathBuf::from(".sessions")),
+        }),
+        _ => None,
+    };
+
+    store.unwrap_or(SessionStoreConfig::Memory)
+}
+
+// Falls back to memory when `user_store` is invalid, the problem is reported.
+fn user_store(config: &mut Config) -> UserStoreConfig {
+    let kind = config.parse_with("user_store", |value| match value {
+        "memory" | "postgres" | "mongodb" => Ok(value.to_string()),
+        _ => Err("expected memory, postgres or mongodb".into()),
+    });
+
+    let store = match kind.as_deref() {
+        Some("postgres") => database_url(config, "user_store_url", POSTGRES_SCHEMES)
+            .map(|url| UserStoreConfig::Postgres { url }),
+        Some("mongodb") => database_url(config, "user_store_url", MONGO_DB_SCHEMES)
+            .map(|url| UserStoreConfig::MongoDb { url }),
+        _ => None,
+    };
+
+    store.unwrap_or(UserStoreConfig::Memory)
+}
+
+const POSTGRES_SCHEMES: &[&str] = &["postgres://", "postgresql://"];
+const MONGO_DB_SCHEMES: &[&str] = &["mongodb://", "mongodb+srv://"];
+
+// A required url `key`, starting with one of `schemes`.
+fn database_url(config: &mut Config, key: &str, schemes: &[&str]) -> Option<String> {
+    config.required(key, |value| parse_database_url(value, schemes))
+}
+
+fn parse_database_url(value: &str, schemes: &[&str]) -> Result<String, String> {
+    match schemes.iter().any(|scheme| value.starts_with(scheme)) {
+        true => Ok(value.to_string()),
+        false => Err(format!("expected a {} url", schemes.join(" or "))),
+    }
+}
+
+fn parse_flash_key(value: &str) -> Result<axum_flash::Key, String> {
+    let bytes = BASE64
+        .decode(value)
+        .map_err(|e| format!("expected base64 ({})", e))?;
+
+    match bytes.len() {
+        64.. => Ok(axum_flash::Key::from(&bytes)),
+        len => Err(format!("expected at least 64 bytes, got {}", len)),
+    }
+}
+
+fn parse_flash_keys(value: &str) -> Result<Vec<axum_flash::Key>, String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+        .map(parse_flash_key)
+        .collect()
+}
+
+/**
+* The flash key for development, when `FLASH_KEY` isn't set. Generated once and kept in `path`,
+* so flashes survive restarts.
+*/
+pub fn dev_flash_key(path: &Path) -> axum_flash::Key {
+    if let Ok(key) = fs::read_to_string(path) {
+        match parse_flash_key(key.trim()) {
+            Ok(key) => return key,
+            Err(e) => tracing::warn!("Replacing the flash key in {:?}: {}", path, e),
+        }
+    }
+
+    let key = axum_flash::Key::generate();
+    if let Err(e) = fs::write(path, BASE64.encode(key.master())) {
+        tracing::warn!("Unable to save the flash key to {:?}: {}", path, e);
+    }
+
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env_vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_defaults() {
+        // Only required in release builds.
+        let flash_key = BASE64.encode([7u8; 64]);
+        let environment =
+            Environment::from_sources(None, &env_vars(&[("FLASH_KEY", &flash_key)])).unwrap();
+
+        assert_eq!(environment.bind_address.to_string(), "0.0.0.0:3000");
+        assert_eq!(environment.session_expiry_minutes.get(), 60);
+        assert!(!environment.session_secure_cookies);
+        assert!(environment.flash_key.is_some());
+        assert!(environment.notifications_mongo_db_url.is_none());
+        assert_eq!(environment.notifications_mongo_db_name, "notifications");
+    }
+
+    #[test]
+    fn test_environment_overrides_config_file() {
+        let config_file = r#"
+            bind_address = "127.0.0.1:8080"
+            session_expiry_minutes = 30
+            session_secure_cookies = true
+        "#;
+        let environment = Environment::from_sources(
+            Some(config_file),
+            &env_vars(&[("SESSION_EXPIRY_MINUTES", "15")]),
+        )
+        .unwrap();
+
+        assert_eq!(environment.bind_address.to_string(), "127.0.0.1:8080");
+        assert_eq!(environment.session_expiry_minutes.get(), 15);
+        assert!(environment.session_secure_cookies);
+    }
+
+    #[test]
+    fn test_lists_every_problem() {
+        let config_file = r#"
+            session_expiry_minutes = 0
+            colour = "blue"
+        "#;
+        let Err(EnvironmentError(problems)) = Environment::from_sources(
+            Some(config_file),
+            &env_vars(&[
+                ("BIND_ADDRESS", "localhost"),
+                ("FLASH_KEY", "c2hvcnQ="),
+                ("NOTIFICATIONS_MONGO_DB_URL", "postgres://localhost"),
+            ]),
+        ) else {
+            panic!("expected the configuration to be invalid");
+        };
+
+        assert_eq!(problems.len(), 5);
+        assert!(problems[0].contains("unknown key `colour`"));
+        assert!(problems.iter().any(|p| p.starts_with("BIND_ADDRESS")));
+        assert!(problems
+            .iter()
+            .any(|p| p.starts_with("SESSION_EXPIRY_MINUTES")));
+        assert!(problems
+            .iter()
+            .any(|p| p.contains("at least 64 bytes, got 5")));
+        assert!(problems
+            .iter()
+            .any(|p| p.contains("mongodb:// or mongodb+srv://")));
+    }
+
+    #[test]
+    fn test_flash_previous_keys() {
+        let previous_keys = format!("{}, {}", BASE64.encode([1u8; 64]), BASE64.encode([2u8; 64]));
+        let environment = Environment::from_sources(
+            None,
+            &env_vars(&[
+                ("FLASH_KEY", &BASE64.encode([3u8; 64])),
+                ("FLASH_PREVIOUS_KEYS", &previous_keys),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(environment.flash_previous_keys.len(), 2);
+        assert_eq!(environment.flash_previous_keys[1].master(), [2u8; 64]);
+    }
+
+    #[test]
+    fn test_session_store() {
+        let flash_key = BASE64.encode([7u8; 64]);
+        let environment = Environment::from_sources(
+            Some(r#"session_store = "postgres""#),
+            &env_vars(&[
+                ("FLASH_KEY", &flash_key),
+                ("SESSION_STORE_URL", "postgres://localhost/wallchart"),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            environment.session_store,
+            SessionStoreConfig::Postgres {
+                url: "postgres://localhost/wallchart".into()
+            }
+        );
+
+        let Err(EnvironmentError(problems)) = Environment::from_sources(
+            None,
+            &env_vars(&[("FLASH_KEY", &flash_key), ("SESSION_STORE", "sqlite")]),
+        ) else {
+            panic!("expected the configuration to be invalid");
+        };
+
+        assert_eq!(
+            problems,
+            vec!["SESSION_STORE_URL (session_store_url): missing"]
+        );
+    }
+
+    #[test]
+    fn test_user_store() {
+        let flash_key = BASE64.encode([7u8; 64]);
+        let environment =
+            Environment::from_sources(None, &env_vars(&[("FLASH_KEY", &flash_key)])).unwrap();
+
+        assert_eq!(environment.user_store, UserStoreConfig::Memory);
+
+        let environment = Environment::from_sources(
+            Some(r#"user_store = "mongodb""#),
+            &env_vars(&[
+                ("FLASH_KEY", &flash_key),
+                ("USER_STORE_URL", "mongodb://localhost:27017/wallchart"),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            environment.user_store,
+            UserStoreConfig::MongoDb {
+                url: "mongodb://localhost:27017/wallchart".into()
+            }
+        );
+
+        let Err(EnvironmentError(problems)) = Environment::from_sources(
+            None,
+            &env_vars(&[
+                ("FLASH_KEY", &flash_key),
+                ("USER_STORE", "postgres"),
+                ("USER_STORE_URL", "mysql://localhost/wallchart"),
+            ]),
+        ) else {
+            panic!("expected the configuration to be invalid");
+        };
+
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("expected a postgres:// or postgresql:// url"));
+    }
+
+    #[test]
+    fn test_dev_flash_key_is_persisted() {
+        let path = env::temp_dir().join(format!("flash-key-{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let key = dev_flash_key(&path);
+
+        assert_eq!(dev_flash_key(&path).master(), key.master());
+        fs::remove_file(&path).unwrap();
     }
 }
@@ -1,66 +1,130 @@
-use auth::Backend;
+use auth::{provide_current_user, Backend};
 use axum::{
-    error_handling::HandleErrorLayer, http::StatusCode, response::IntoResponse, routing::get,
-    BoxError, Router,
+    error_handling::HandleErrorLayer, http::StatusCode, middleware, response::IntoResponse,
+    routing::get, BoxError, Router,
 };
 use axum_login::{tower_sessions::SessionManagerLayer, AuthManagerLayerBuilder};
 use chrono::prelude::*;
-use environment::load_environment;
-use std::{net::SocketAddr, sync::Arc};
+use environment::{dev_flash_key, load_dotenv, load_environment};
+use in_memory_notification_repository::InMemoryNotificationRepository;
+use mongo_notification_repository::mongo_notification_repository::MongoNotificationRepository;
+use notifications_service::{
+    ports::notification_repository::NotificationRepository, service::NotificationsService,
+};
+use sessions::Sessions;
+use std::{process::exit, sync::Arc, time::Duration as StdDuration};
+use telemetry::init_tracing;
 use tower::ServiceBuilder;
 
-use tower_sessions::{cookie::time::Duration, mongodb::Client, Expiry, MemoryStore, MongoDBStore};
-use web_htmx::{livereload, routes as web_routes, state::WebHtmxState};
+use tower_sessions::{cookie::time::Duration, mongodb::Client, Expiry};
+use users::connect_user_repository;
+use users_service::ports::user_repository::UserRepository;
+use web_htmx::{
+    csp::ContentSecurityPolicy, flash::FlashKeys, livereload, routes as web_routes,
+    state::WebHtmxState, trace::request_span_layer,
+};
 
 mod auth;
 mod environment;
+mod sessions;
+mod telemetry;
+mod users;
 
 #[tokio::main]
 async fn main() {
-    let env = load_environment();
+    load_dotenv();
+    // First, so loading the environment is traced too.
+    init_tracing();
+    let env = load_environment().unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        exit(1);
+    });
 
     // Create WebHtmxState
     // This is how you can inject dependencies into the web-htmx crate
     // like a backend service
     // TODO: include an example
+    let notification_repository: Arc<dyn NotificationRepository> =
+        match &env.notifications_mongo_db_url {
+            Some(url) => {
+                let client = Client::with_uri_str(url)
+                    .await
+                    .expect("Failed to create notifications MongoDB client");
+                Arc::new(MongoNotificationRepository::new(
+                    &client.database(&env.notifications_mongo_db_name),
+                ))
+            }
+            None => Arc::new(InMemoryNotificationRepository::empty()),
+        };
+
     let web_htmx_state = WebHtmxState {
-        flash_config: axum_flash::Config::new(axum_flash::Key::generate()),
+        flash_keys: FlashKeys::new(
+            env.flash_key
+                .clone()
+                .unwrap_or_else(|| dev_flash_key(&env.flash_key_file)),
+        )
+        .previous(env.flash_previous_keys.clone()),
+        notifications_service: NotificationsService::new(notification_repository),
+        content_security_policy: content_security_policy(),
     };
 
     let app = Router::new()
-        .merge(web_routes(web_htmx_state))
+        .merge(web_routes::<Backend<dyn UserRepository>>(web_htmx_state))
         .route("/healthcheck", get(get_health_check));
 
     // Auth and session setup
-    let session_store = MemoryStore::default();
-    let session_service = ServiceBuilder::new()
-        .layer(HandleErrorLayer::new(|_: BoxError| async {
-            StatusCode::BAD_REQUEST
-        }))
-        .layer(SessionManagerLayer::new(session_store.clone()).with_secure(false));
+    let session_store = Sessions::connect(&env.session_store)
+        .await
+        .expect("Failed to set up the session store");
+    session_store.spawn_cleanup_task(StdDuration::from_secs(
+        u64::from(env.session_cleanup_minutes.get()) * 60,
+    ));
 
-    let user_memory_store = Backend::default();
+    let user_repository = connect_user_repository(&env.user_store)
+        .await
+        .expect("Failed to set up the user store");
+    let backend = Backend::new(user_repository);
     let session_layer = SessionManagerLayer::new(session_store)
-        .with_secure(false)
-        .with_expiry(Expiry::OnInactivity(Duration::hours(1)));
+        .with_secure(env.session_secure_cookies)
+        .with_expiry(Expiry::OnInactivity(Duration::minutes(
+            env.session_expiry_minutes.get().into(),
+        )));
     let auth_layer = ServiceBuilder::new()
         .layer(HandleErrorLayer::new(|_: BoxError| async {
             StatusCode::BAD_REQUEST
         }))
-        .layer(AuthManagerLayerBuilder::new(user_memory_store, session_layer).build());
+        .layer(AuthManagerLayerBuilder::new(backend, session_layer).build());
+    // Inside of the auth layer, it needs the auth session.
+    let app = app.layer(middleware::from_fn(
+        provide_current_user::<dyn UserRepository>,
+    ));
     let app = app.layer(auth_layer);
-    let app = app.layer(session_service);
 
     #[cfg(debug_assertions)]
     let app = app.layer(livereload::layer());
 
-    let addr = SocketAddr::from(([0, 0, 0, 0], 3000));
-    let listener = tokio::net::TcpListener::bind(&addr).await.unwrap();
+    // Outermost, so the latency covers the other layers too.
+    let app = app.layer(middleware::from_fn(request_span_layer));
+
+    let listener = tokio::net::TcpListener::bind(&env.bind_address)
+        .await
+        .unwrap();
+    tracing::info!("Listening on {}", env.bind_address);
     axum::serve(listener, app.into_make_service())
         .await
         .expect("Failed to start server");
 }
 
+// Livereload injects an inline script without our nonce, only report violations in development.
+fn content_security_policy() -> ContentSecurityPolicy {
+    let policy = ContentSecurityPolicy::default();
+
+    #[cfg(debug_assertions)]
+    let policy = policy.report_only();
+
+    policy
+}
+
 async fn get_health_check() -> impl IntoResponse {
     "OK"
 }
@@ -0,0 +1,307 @@
+use std::{path::PathBuf, time::Duration};
+
+use axum::async_trait;
+use tower_sessions::{
+    mongodb::Client,
+    session::{Id, Session},
+    session_store::ExpiredDeletion,
+    sqlx::{PgPool, SqlitePool},
+    MemoryStore, MongoDBStore, PostgresStore, SessionStore, SqliteStore,
+};
+
+use file_store::FileStore;
+
+pub mod file_store;
+
+/**
+* Where sessions are kept, picked with `SESSION_STORE` and `SESSION_STORE_URL`.
+*/
+#[derive(Clone, Debug, PartialEq)]
+pub enum SessionStoreConfig {
+    // Lost on restart, development only.
+    Memory,
+    MongoDb { url: String },
+    Postgres { url: String },
+    // ex: `sqlite://sessions.db?mode=rwc`
+    Sqlite { url: String },
+    // A json file per session in `dir`, for local development.
+    File { dir: PathBuf },
+}
+
+/**
+* The configured session store, so main builds a single `SessionManagerLayer` whichever it is.
+*/
+#[derive(Clone, Debug)]
+pub enum Sessions {
+    Memory(MemoryStore),
+    MongoDb(MongoDBStore),
+    Postgres(PostgresStore),
+    Sqlite(SqliteStore),
+    File(FileStore),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SessionsError {
+    #[error("MongoDB session store: {0}")]
+    MongoDb(<MongoDBStore as SessionStore>::Error),
+    #[error("Postgres session store: {0}")]
+    Postgres(<PostgresStore as SessionStore>::Error),
+    #[error("SQLite session store: {0}")]
+    Sqlite(<SqliteStore as SessionStore>::Error),
+    #[error("file session store: {0}")]
+    File(<FileStore as SessionStore>::Error),
+    #[error("unable to set up the session store: {0}")]
+    Setup(String),
+}
+
+impl Sessions {
+    // Connects to the store, creating its tables when it needs any.
+    pub async fn connect(config: &SessionStoreConfig) -> Result<Self, SessionsError> {
+        let setup = |e: &dyn std::error::Error| SessionsError::Setup(e.to_string());
+
+        Ok(match config {
+            SessionStoreConfig::Memory => Sessions::Memory(MemoryStore::default()),
+            SessionStoreConfig::MongoDb { url } => {
+                let client = Client::with_uri_str(url).await.map_err(|e| setup(&e))?;
+                let database = client
+                    .default_database()
+                    .map(|database| database.name().to_string())
+                    .unwrap_or("sessions".into());
+
+                Sessions::MongoDb(MongoDBStore::new(client, database))
+            }
+            SessionStoreConfig::Postgres { url } => {
+                let pool = PgPool::connect(url).await.map_err(|e| setup(&e))?;
+                let store = PostgresStore::new(pool);
+                store.migrate().await.map_err(|e| setup(&e))?;
+
+                Sessions::Postgres(store)
+            }
+            SessionStoreConfig::Sqlite { url } => {
+                let pool = SqlitePool::connect(url).await.map_err(|e| setup(&e))?;
+                let store = SqliteStore::new(pool);
+                store.migrate().await.map_err(|e| setup(&e))?;
+
+                Sessions::Sqlite(store)
+            }
+            SessionStoreConfig::File { dir } => {
+                Sessions::File(FileStore::new(dir).await.map_err(|e| setup(&e))?)
+            }
+        })
+    }
+
+    // Deletes expired sessions every `period`, in the background. Memory sessions are only
+    // dropped on restart.
+    pub fn spawn_cleanup_task(&self, period: Duration) {
+        if let Sessions::Memory(_) = self {
+            return;
+        }
+
+        let sessions = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = sessions.continuously_delete_expired(period).await {
+                tracing::error!("Stopped deleting expired sessions: {}", e);
+            }
+        });
+    }
+}
+
+#[async_trait]
+impl SessionStore for Sessions {
+    type Error = SessionsError;
+
+    async fn save(&self, session: &Session) -> Result<(), Self::Error> {
+        match self {
+            Sessions::Memory(store) => store.save(session).await.map_err(|e| match e {}),
+            Sessions::MongoDb(store) => store.save(session).await.map_err(SessionsError::MongoDb),
+            Sessions::Postgres(store) => store.save(session).await.map_err(SessionsError::Postgres),
+            Sessions::Sqlite(store) => store.save(session).await.map_err(SessionsError::Sqlite),
+            Sessions::File(store) => store.save(session).await.map_err(SessionsError::File),
+        }
+    }
+
+    async fn load(&self, session_id: &Id) -> Result<Option<Session>, Self::Error> {
+        match self {
+            Sessions::Memory(store) => store.load(session_id).await.map_err(|e| match e {}),
+            Sessions::MongoDb(store) => {
+                store.load(session_id).await.map_err(SessionsError::MongoDb)
+            }
+            Sessions::Postgres(store) => store
+                .load(session_id)
+                .await
+                .map_err(SessionsError::Postgres),
+            Sessions::Sqlite(store) => store.load(session_id).await.map_err(SessionsError::Sqlite),
+            Sessions::File(store) => store.load(session_id).await.map_err(SessionsError::File),
+        }
+    }
+
+    async fn delete(&self, session_id: &Id) -> Result<(), Self::Error> {
+        match self {
+            Sessions::Memory(store) => store.delete(session_id).await.map_err(|e| match e {}),
+            Sessions::MongoDb(store) => store
+                .delete(session_id)
+                .await
+                .map_err(SessionsError::MongoDb),
+            Sessions::Postgres(store) => store
+                .delete(session_id)
+                .await
+                .map_err(SessionsError::Postgres),
+            Sessions::Sqlite(store) => store
+                .delete(session_id)
+                .await
+                .map_err(SessionsError::Sqlite),
+            Sessions::File(store) => store.delete(session_id).await.map_err(SessionsError::File),
+        }
+    }
+}
+
+#[async_trait]
+impl ExpiredDeletion for Sessions {
+    async fn delete_expired(&self) -> Result<(), Self::Error> {
+        match self {
+            // Expired sessions aren't loaded, they're dropped with the process.
+            Sessions::Memory(_) => Ok(()),
+            Sessions::MongoDb(store) => {
+                store.delete_expired().await.map_err(SessionsError::MongoDb)
+            }
+            Sessions::Postgres(store) => store
+                .delete_expired()
+                .await
+                .map_err(SessionsError::Postgres),
+            Sessions::Sqlite(store) => store.delete_expired().await.map_err(SessionsError::Sqlite),
+            Sessions::File(store) => store.delete_expired().await.map_err(SessionsError::File),
+        }
+    }
+}
+
+/**
+* Every store gets the same checks. Memory, SQLite and file stores run with `cargo test`, MongoDB
+* and Postgres need a database (`docker compose up`) and run with
+* `cargo test -- --ignored` and `SESSIONS_TEST_MONGO_DB_URL` / `SESSIONS_TEST_POSTGRES_URL`.
+*/
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tower_sessions::{cookie::time::Duration as CookieDuration, Expiry};
+
+    fn session_expiring_in(seconds: i64) -> Session {
+        let session = Session::new(Some(Expiry::OnInactivity(CookieDuration::seconds(seconds))));
+        session.insert("user_id", 42).unwrap();
+        session
+    }
+
+    async fn check_store(config: SessionStoreConfig) {
+        let sessions = Sessions::connect(&config).await.unwrap();
+
+        let session = session_expiring_in(60);
+        sessions.save(&session).await.unwrap();
+        let loaded = sessions.load(session.id()).await.unwrap().unwrap();
+        assert_eq!(loaded.get::<i32>("user_id").unwrap(), Some(42));
+
+        let expired = session_expiring_in(-60);
+        sessions.save(&expired).await.unwrap();
+        assert!(sessions.load(expired.id()).await.unwrap().is_none());
+
+        sessions.delete_expired().await.unwrap();
+        assert!(sessions.load(session.id()).await.unwrap().is_some());
+
+        sessions.delete(session.id()).await.unwrap();
+        assert!(sessions.load(session.id()).await.unwrap().is_none());
+    }
+
+    fn test_url(name: &str) -> String {
+        std::env::var(name).unwrap_or_else(|_| panic!("{} must be set", name))
+    }
+
+    #[tokio::test]
+    async fn test_memory_store() {
+        check_store(SessionStoreConfig::Memory).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_store() {
+        check_store(SessionStoreConfig::Sqlite {
+            url: "sqlite::memory:".into(),
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_file_store() {
+        let dir = std::env::temp_dir().join(format!("sessions-{}", unique_suffix()));
+        check_store(SessionStoreConfig::File { dir: dir.clone() }).await;
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_file_store_concurrent_saves() {
+        let dir = std::env::temp_dir().join(format!("sessions-{}", unique_suffix()));
+        let sessions = Sessions::connect(&SessionStoreConfig::File { dir: dir.clone() })
+            .await
+            .unwrap();
+        let session = session_expiring_in(60);
+
+        let mut saves = tokio::task::JoinSet::new();
+        for _ in 0..20 {
+            let (sessions, session) = (sessions.clone(), session.clone());
+            saves.spawn(async move { sessions.save(&session).await });
+        }
+        while let Some(saved) = saves.join_next().await {
+            saved.unwrap().unwrap();
+        }
+
+        assert!(sessions.load(session.id()).await.unwrap().is_some());
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_file_store_deletes_abandoned_tmp_files() {
+        let dir = std::env::temp_dir().join(format!("sessions-{}", unique_suffix()));
+        let sessions = Sessions::connect(&SessionStoreConfig::File { dir: dir.clone() })
+            .await
+            .unwrap();
+
+        let abandoned = dir.join("abandoned.json.0123456789abcdef.tmp");
+        let in_progress = dir.join("in-progress.json.0123456789abcdef.tmp");
+        let other = dir.join("notes.txt");
+        for path in [&abandoned, &in_progress, &other] {
+            std::fs::write(path, b"{").unwrap();
+        }
+        std::fs::File::options()
+            .write(true)
+            .open(&abandoned)
+            .unwrap()
+            .set_modified(std::time::SystemTime::now() - std::time::Duration::from_secs(3600))
+            .unwrap();
+
+        sessions.delete_expired().await.unwrap();
+
+        assert!(!abandoned.exists());
+        assert!(in_progress.exists());
+        assert!(other.exists());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs SESSIONS_TEST_MONGO_DB_URL"]
+    async fn test_mongo_db_store() {
+        check_store(SessionStoreConfig::MongoDb {
+            url: test_url("SESSIONS_TEST_MONGO_DB_URL"),
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    #[ignore = "needs SESSIONS_TEST_POSTGRES_URL"]
+    async fn test_postgres_store() {
+        check_store(SessionStoreConfig::Postgres {
+            url: test_url("SESSIONS_TEST_POSTGRES_URL"),
+        })
+        .await;
+    }
+
+    fn unique_suffix() -> String {
+        format!("{}-{}", std::process::id(), rand::random::<u32>())
+    }
+}
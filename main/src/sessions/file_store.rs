@@ -0,0 +1,152 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use axum::async_trait;
+use serde::{Deserialize, Serialize};
+use tokio::fs::DirEntry;
+use tower_sessions::{
+    cookie::time::OffsetDateTime,
+    session::{Id, Session},
+    session_store::ExpiredDeletion,
+    SessionStore,
+};
+
+// Saves take milliseconds, a temporary file this old was abandoned.
+const TMP_FILE_MAX_AGE: Duration = Duration::from_secs(60);
+
+/**
+* Keeps each session in its own json file, so local development keeps its sessions across
+* restarts without a database.
+*/
+#[derive(Clone, Debug)]
+pub struct FileStore {
+    dir: PathBuf,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum FileStoreError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+#[derive(Serialize, Deserialize)]
+struct SessionRecord {
+    // Unix timestamp, so expired files can be found without the session.
+    expiry_date: i64,
+    session: Session,
+}
+
+impl SessionRecord {
+    fn is_active(&self) -> bool {
+        self.expiry_date > OffsetDateTime::now_utc().unix_timestamp()
+    }
+}
+
+impl FileStore {
+    // Creates `dir` if it doesn't exist.
+    pub async fn new(dir: impl AsRef<Path>) -> Result<Self, FileStoreError> {
+        let dir = dir.as_ref().to_path_buf();
+        tokio::fs::create_dir_all(&dir).await?;
+
+        Ok(Self { dir })
+    }
+
+    fn path(&self, session_id: &Id) -> PathBuf {
+        self.dir.join(format!("{}.json", session_id.0))
+    }
+
+    async fn is_older_than(entry: &DirEntry, age: Duration) -> Result<bool, FileStoreError> {
+        let modified = entry.metadata().await?.modified()?;
+
+        Ok(modified.elapsed().is_ok_and(|elapsed| elapsed > age))
+    }
+
+    async fn delete_if_stale(entry: &DirEntry) -> Result<(), FileStoreError> {
+        let path = entry.path();
+        let is_stale = match path.extension() {
+            // Unreadable files are removed too, they'd never load.
+            Some(extension) if extension == "json" => {
+                !matches!(Self::read(&path).await, Ok(Some(record)) if record.is_active())
+            }
+            // Left behind by a save that never got to rename it. Recent ones may still be.
+            Some(extension) if extension == "tmp" => {
+                Self::is_older_than(entry, TMP_FILE_MAX_AGE).await?
+            }
+            _ => false,
+        };
+
+        if is_stale {
+            match tokio::fs::remove_file(path).await {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e.into()),
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn read(path: &Path) -> Result<Option<SessionRecord>, FileStoreError> {
+        match tokio::fs::read(path).await {
+            Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+#[async_trait]
+impl SessionStore for FileStore {
+    type Error = FileStoreError;
+
+    async fn save(&self, session: &Session) -> Result<(), Self::Error> {
+        let record = SessionRecord {
+            expiry_date: session.expiry_date().unix_timestamp(),
+            session: session.clone(),
+        };
+
+        // Written next to it then renamed, so a session is never read half written. Each save has
+        // its own temporary file, concurrent saves of a session can't mix their writes.
+        let path = self.path(session.id());
+        let tmp_path = path.with_extension(format!("json.{:016x}.tmp", rand::random::<u64>()));
+        tokio::fs::write(&tmp_path, serde_json::to_vec(&record)?).await?;
+        tokio::fs::rename(tmp_path, path).await?;
+
+        Ok(())
+    }
+
+    async fn load(&self, session_id: &Id) -> Result<Option<Session>, Self::Error> {
+        let record = Self::read(&self.path(session_id)).await?;
+
+        Ok(record
+            .filter(SessionRecord::is_active)
+            .map(|record| record.session))
+    }
+
+    async fn delete(&self, session_id: &Id) -> Result<(), Self::Error> {
+        match tokio::fs::remove_file(self.path(session_id)).await {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[async_trait]
+impl ExpiredDeletion for FileStore {
+    async fn delete_expired(&self) -> Result<(), Self::Error> {
+        let mut entries = tokio::fs::read_dir(&self.dir).await?;
+
+        while let Some(entry) = entries.next_entry().await? {
+            // One bad file shouldn't keep the others around.
+            if let Err(e) = Self::delete_if_stale(&entry).await {
+                tracing::warn!(path = ?entry.path(), error = %e, "failed to clean up session file");
+            }
+        }
+
+        Ok(())
+    }
+}
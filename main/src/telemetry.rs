@@ -0,0 +1,37 @@
+use std::env;
+
+use tracing_subscriber::{fmt, prelude::*, EnvFilter};
+
+// Used when `RUST_LOG` isn't set.
+const DEFAULT_FILTER: &str = "info";
+
+/**
+* Installs the global tracing subscriber. Logs are pretty printed in development and JSON (one
+* object per line) in release builds, `LOG_FORMAT=pretty|json` overrides it. Filter them with
+* `RUST_LOG` (ex: `RUST_LOG=web_htmx=debug,info`).
+*/
+pub fn init_tracing() {
+    let filter =
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER));
+    let registry = tracing_subscriber::registry().with(filter);
+
+    match log_format().as_str() {
+        "json" => registry
+            .with(
+                fmt::layer()
+                    .json()
+                    .flatten_event(true)
+                    .with_current_span(true)
+                    .with_span_list(false),
+            )
+            .init(),
+        _ => registry.with(fmt::layer().pretty()).init(),
+    }
+}
+
+fn log_format() -> String {
+    env::var("LOG_FORMAT").unwrap_or_else(|_| match cfg!(debug_assertions) {
+        true => "pretty".into(),
+        false => "json".into(),
+    })
+}
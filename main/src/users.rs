@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use diesel_user_repository::diesel_user_repository::DieselUserRepository;
+use in_memory_user_repository::InMemoryUserRepository;
+use mongo_user_repository::mongo_user_repository::MongoUserRepository;
+use tower_sessions::mongodb::Client;
+use users_service::{
+    models::User,
+    ports::user_repository::{RepositoryFailure, UserRepository},
+};
+
+/**
+* Where users are kept, picked with `USER_STORE` and `USER_STORE_URL`.
+*/
+#[derive(Clone, Debug, PartialEq)]
+pub enum UserStoreConfig {
+    // Lost on restart. Development builds start with a demo user.
+    Memory,
+    Postgres { url: String },
+    MongoDb { url: String },
+}
+
+// Connects to the store, creating its tables (or indexes) when it needs any.
+pub async fn connect_user_repository(
+    config: &UserStoreConfig,
+) -> Result<Arc<dyn UserRepository>, RepositoryFailure> {
+    let unknown = |e: &dyn std::error::Error| RepositoryFailure::Unknown(e.to_string());
+
+    Ok(match config {
+        UserStoreConfig::Memory => Arc::new(InMemoryUserRepository::with(dev_users())),
+        UserStoreConfig::Postgres { url } => Arc::new(DieselUserRepository::connect(url).await?),
+        UserStoreConfig::MongoDb { url } => {
+            let client = Client::with_uri_str(url).await.map_err(|e| unknown(&e))?;
+            let database = client
+                .default_database()
+                .unwrap_or_else(|| client.database("users"));
+            let repository = MongoUserRepository::new(&database);
+            repository.create_indexes().await?;
+
+            Arc::new(repository)
+        }
+    })
+}
+
+#[cfg(debug_assertions)]
+fn dev_users() -> Vec<User> {
+    tracing::info!("Users are kept in memory, sign in as demo (password: demo)");
+    vec![User::new("demo", "demo")]
+}
+
+#[cfg(not(debug_assertions))]
+fn dev_users() -> Vec<User> {
+    vec![]
+}
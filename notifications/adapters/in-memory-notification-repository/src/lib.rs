@@ -0,0 +1,243 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use notifications_service::models::Notification;
+use notifications_service::ports::notification_repository::{
+    NotificationRepository, RepositoryFailure,
+};
+use tokio::sync::RwLock;
+
+#[derive(Clone, Debug)]
+pub struct InMemoryNotificationRepository {
+    pub notifications: Arc<RwLock<Vec<Notification>>>,
+}
+
+impl InMemoryNotificationRepository {
+    pub fn empty() -> Self {
+        Self {
+            notifications: Arc::new(RwLock::new(vec![])),
+        }
+    }
+
+    pub fn with(notifications: Vec<Notification>) -> Self {
+        Self {
+            notifications: Arc::new(RwLock::new(notifications)),
+        }
+    }
+}
+
+#[async_trait]
+impl NotificationRepository for InMemoryNotificationRepository {
+    async fn get_notification(
+        &self,
+        id: String,
+    ) -> Result<Option<Notification>, RepositoryFailure> {
+        let notifications = self.notifications.read().await;
+        Ok(notifications
+            .iter()
+            .find(|n| n.id == id)
+            .map(|n| n.to_owned()))
+    }
+
+    async fn get_notifications_for_user(
+        &self,
+        user_id: String,
+    ) -> Result<Vec<Notification>, RepositoryFailure> {
+        let notifications = self.notifications.read().await;
+        let mut user_notifications: Vec<Notification> = notifications
+            .iter()
+            .filter(|n| n.user_id == user_id)
+            .map(|n| n.to_owned())
+            .collect();
+
+        user_notifications.sort_by_key(|n| std::cmp::Reverse(n.created_at));
+        Ok(user_notifications)
+    }
+
+    async fn count_unread_notifications(
+        &self,
+        user_id: String,
+    ) -> Result<usize, RepositoryFailure> {
+        let notifications = self.notifications.read().await;
+        Ok(notifications
+            .iter()
+            .filter(|n| n.user_id == user_id && !n.is_read())
+            .count())
+    }
+
+    async fn save(&self, notification: Notification) -> Result<(), RepositoryFailure> {
+        let mut notifications = self.notifications.write().await;
+
+        notifications.retain(|n| n.id != notification.id);
+        notifications.push(notification);
+
+        Ok(())
+    }
+
+    async fn mark_all_read(
+        &self,
+        user_id: String,
+        read_at: DateTime<Utc>,
+    ) -> Result<(), RepositoryFailure> {
+        let mut notifications = self.notifications.write().await;
+
+        notifications
+            .iter_mut()
+            .filter(|n| n.user_id == user_id)
+            .for_each(|n| n.mark_read(read_at));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use notifications_service::{
+        count_unread_notifications::CountUnreadNotificationsInput,
+        get_notifications::GetNotificationsInput,
+        mark_all_notifications_read::MarkAllNotificationsReadInput,
+        mark_notification_read::{MarkNotificationReadFailure, MarkNotificationReadInput},
+        publish_notification::PublishNotificationInput,
+        service::NotificationsService,
+    };
+
+    fn notification(id: &str, user_id: &str, minutes_ago: i64) -> Notification {
+        Notification {
+            id: id.into(),
+            user_id: user_id.into(),
+            title: format!("Title {}", id),
+            message: format!("Message {}", id),
+            url: None,
+            created_at: Utc::now() - Duration::minutes(minutes_ago),
+            read_at: None,
+        }
+    }
+
+    fn service(repository: InMemoryNotificationRepository) -> NotificationsService {
+        NotificationsService::new(Arc::new(repository))
+    }
+
+    fn unread(user_id: &str) -> CountUnreadNotificationsInput {
+        CountUnreadNotificationsInput {
+            user_id: user_id.into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_notifications_are_per_user_and_newest_first() {
+        let service = service(InMemoryNotificationRepository::with(vec![
+            notification("1", "ann", 10),
+            notification("2", "bob", 5),
+            notification("3", "ann", 1),
+        ]));
+
+        let notifications = service
+            .get_notifications(GetNotificationsInput {
+                user_id: "ann".into(),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(
+            notifications
+                .iter()
+                .map(|n| n.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["3", "1"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_publish_notification() {
+        let service = service(InMemoryNotificationRepository::empty());
+
+        let published = service
+            .publish_notification(PublishNotificationInput {
+                user_id: "ann".into(),
+                title: "Shift changed".into(),
+                message: "Your Tuesday shift starts at 9am.".into(),
+                url: Some("/wallchart".into()),
+            })
+            .await
+            .unwrap();
+
+        assert!(!published.is_read());
+        assert_eq!(
+            service.count_unread_notifications(unread("ann")).await,
+            Ok(1)
+        );
+        assert_eq!(
+            service.count_unread_notifications(unread("bob")).await,
+            Ok(0)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mark_notification_read() {
+        let service = service(InMemoryNotificationRepository::with(vec![
+            notification("1", "ann", 10),
+            notification("2", "ann", 5),
+        ]));
+
+        service
+            .mark_notification_read(MarkNotificationReadInput {
+                user_id: "ann".into(),
+                notification_id: "1".into(),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(
+            service.count_unread_notifications(unread("ann")).await,
+            Ok(1)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mark_someone_elses_notification_read() {
+        let service = service(InMemoryNotificationRepository::with(vec![notification(
+            "1", "ann", 10,
+        )]));
+
+        let result = service
+            .mark_notification_read(MarkNotificationReadInput {
+                user_id: "bob".into(),
+                notification_id: "1".into(),
+            })
+            .await;
+
+        assert_eq!(result, Err(MarkNotificationReadFailure::NotFound));
+        assert_eq!(
+            service.count_unread_notifications(unread("ann")).await,
+            Ok(1)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mark_all_notifications_read() {
+        let service = service(InMemoryNotificationRepository::with(vec![
+            notification("1", "ann", 10),
+            notification("2", "ann", 5),
+            notification("3", "bob", 5),
+        ]));
+
+        service
+            .mark_all_notifications_read(MarkAllNotificationsReadInput {
+                user_id: "ann".into(),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(
+            service.count_unread_notifications(unread("ann")).await,
+            Ok(0)
+        );
+        assert_eq!(
+            service.count_unread_notifications(unread("bob")).await,
+            Ok(1)
+        );
+    }
+}
@@ -0,0 +1,2 @@
+pub mod mongo_notification_repository;
+mod records;
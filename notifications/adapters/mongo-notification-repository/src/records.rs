@@ -0,0 +1,42 @@
+use notifications_service::models::Notification;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct NotificationRecord {
+    #[serde(rename = "_id")]
+    pub id: String,
+    pub user_id: String,
+    pub title: String,
+    pub message: String,
+    pub url: Option<String>,
+    pub created_at: bson::DateTime,
+    pub read_at: Option<bson::DateTime>,
+}
+
+impl From<Notification> for NotificationRecord {
+    fn from(notification: Notification) -> Self {
+        Self {
+            id: notification.id,
+            user_id: notification.user_id,
+            title: notification.title,
+            message: notification.message,
+            url: notification.url,
+            created_at: bson::DateTime::from_chrono(notification.created_at),
+            read_at: notification.read_at.map(bson::DateTime::from_chrono),
+        }
+    }
+}
+
+impl From<NotificationRecord> for Notification {
+    fn from(record: NotificationRecord) -> Self {
+        Self {
+            id: record.id,
+            user_id: record.user_id,
+            title: record.title,
+            message: record.message,
+            url: record.url,
+            created_at: record.created_at.to_chrono(),
+            read_at: record.read_at.map(|read_at| read_at.to_chrono()),
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+use thiserror::Error;
+
+use crate::ports::notification_repository::NotificationRepository;
+
+#[derive(Clone)]
+pub struct CountUnreadNotifications {
+    pub notification_repository: Arc<dyn NotificationRepository>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CountUnreadNotificationsInput {
+    pub user_id: String,
+}
+
+pub type CountUnreadNotificationsOutput = Result<usize, CountUnreadNotificationsFailure>;
+
+impl CountUnreadNotifications {
+    pub async fn count_unread_notifications(
+        &self,
+        input: CountUnreadNotificationsInput,
+    ) -> CountUnreadNotificationsOutput {
+        self.notification_repository
+            .count_unread_notifications(input.user_id)
+            .await
+            .map_err(|e| CountUnreadNotificationsFailure::Unknown(e.to_string()))
+    }
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum CountUnreadNotificationsFailure {
+    #[error("Something went wrong")]
+    Unknown(String),
+}
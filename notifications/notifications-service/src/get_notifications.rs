@@ -0,0 +1,33 @@
+use std::sync::Arc;
+
+use thiserror::Error;
+
+use crate::models::Notification;
+use crate::ports::notification_repository::NotificationRepository;
+
+#[derive(Clone)]
+pub struct GetNotifications {
+    pub notification_repository: Arc<dyn NotificationRepository>,
+}
+
+#[derive(Clone, Debug)]
+pub struct GetNotificationsInput {
+    pub user_id: String,
+}
+
+pub type GetNotificationsOutput = Result<Vec<Notification>, GetNotificationsFailure>;
+
+impl GetNotifications {
+    pub async fn get_notifications(&self, input: GetNotificationsInput) -> GetNotificationsOutput {
+        self.notification_repository
+            .get_notifications_for_user(input.user_id)
+            .await
+            .map_err(|e| GetNotificationsFailure::Unknown(e.to_string()))
+    }
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum GetNotificationsFailure {
+    #[error("Something went wrong")]
+    Unknown(String),
+}
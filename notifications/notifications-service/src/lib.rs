@@ -0,0 +1,9 @@
+//##PLOP INSERT MOD HOOK##
+pub mod count_unread_notifications;
+pub mod get_notifications;
+pub mod mark_all_notifications_read;
+pub mod mark_notification_read;
+pub mod models;
+pub mod ports;
+pub mod publish_notification;
+pub mod service;
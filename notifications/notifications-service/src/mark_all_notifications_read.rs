@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use thiserror::Error;
+
+use crate::ports::notification_repository::NotificationRepository;
+
+#[derive(Clone)]
+pub struct MarkAllNotificationsRead {
+    pub notification_repository: Arc<dyn NotificationRepository>,
+}
+
+#[derive(Clone, Debug)]
+pub struct MarkAllNotificationsReadInput {
+    pub user_id: String,
+}
+
+pub type MarkAllNotificationsReadOutput = Result<(), MarkAllNotificationsReadFailure>;
+
+impl MarkAllNotificationsRead {
+    pub async fn mark_all_notifications_read(
+        &self,
+        input: MarkAllNotificationsReadInput,
+    ) -> MarkAllNotificationsReadOutput {
+        self.notification_repository
+            .mark_all_read(input.user_id, Utc::now())
+            .await
+            .map_err(|e| MarkAllNotificationsReadFailure::Unknown(e.to_string()))
+    }
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum MarkAllNotificationsReadFailure {
+    #[error("Something went wrong")]
+    Unknown(String),
+}
@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use thiserror::Error;
+
+use crate::ports::notification_repository::NotificationRepository;
+
+#[derive(Clone)]
+pub struct MarkNotificationRead {
+    pub notification_repository: Arc<dyn NotificationRepository>,
+}
+
+#[derive(Clone, Debug)]
+pub struct MarkNotificationReadInput {
+    pub user_id: String,
+    pub notification_id: String,
+}
+
+pub type MarkNotificationReadOutput = Result<(), MarkNotificationReadFailure>;
+
+impl MarkNotificationRead {
+    pub async fn mark_notification_read(
+        &self,
+        input: MarkNotificationReadInput,
+    ) -> MarkNotificationReadOutput {
+        let mut notification = self
+            .notification_repository
+            .get_notification(input.notification_id)
+            .await
+            .map_err(|e| MarkNotificationReadFailure::Unknown(e.to_string()))?
+            // Someone else's notification is as good as missing.
+            .filter(|notification| notification.user_id == input.user_id)
+            .ok_or(MarkNotificationReadFailure::NotFound)?;
+
+        if notification.is_read() {
+            return Ok(());
+        }
+
+        notification.mark_read(Utc::now());
+
+        self.notification_repository
+            .save(notification)
+            .await
+            .map_err(|e| MarkNotificationReadFailure::Unknown(e.to_string()))
+    }
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum MarkNotificationReadFailure {
+    #[error("Notification does not exist")]
+    NotFound,
+    #[error("Something went wrong")]
+    Unknown(String),
+}
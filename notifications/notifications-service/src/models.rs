@@ -0,0 +1,29 @@
+use chrono::{DateTime, Utc};
+
+/**
+ * Notification
+ *
+ * A message for one user, kept until they have read it (and after).
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct Notification {
+    pub id: String,
+    pub user_id: String,
+    pub title: String,
+    pub message: String,
+    // Where the notification takes you, if anywhere.
+    pub url: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub read_at: Option<DateTime<Utc>>,
+}
+
+impl Notification {
+    pub fn is_read(&self) -> bool {
+        self.read_at.is_some()
+    }
+
+    // Keeps the first time a notification was read.
+    pub fn mark_read(&mut self, read_at: DateTime<Utc>) {
+        self.read_at.get_or_insert(read_at);
+    }
+}
@@ -0,0 +1,36 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+
+use crate::models::Notification;
+
+#[async_trait]
+pub trait NotificationRepository: Send + Sync + 'static {
+    async fn get_notification(&self, id: String)
+        -> Result<Option<Notification>, RepositoryFailure>;
+
+    // Newest first.
+    async fn get_notifications_for_user(
+        &self,
+        user_id: String,
+    ) -> Result<Vec<Notification>, RepositoryFailure>;
+
+    async fn count_unread_notifications(&self, user_id: String)
+        -> Result<usize, RepositoryFailure>;
+
+    async fn save(&self, notification: Notification) -> Result<(), RepositoryFailure>;
+
+    async fn mark_all_read(
+        &self,
+        user_id: String,
+        read_at: DateTime<Utc>,
+    ) -> Result<(), RepositoryFailure>;
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum RepositoryFailure {
+    #[error("Failed to get connection from pool")]
+    FailedToGetConnectionFromPool,
+    #[error("Something went wrong")]
+    Unknown(String),
+}
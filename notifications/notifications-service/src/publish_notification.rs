@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use thiserror::Error;
+
+use crate::models::Notification;
+use crate::ports::notification_repository::NotificationRepository;
+
+#[derive(Clone)]
+pub struct PublishNotification {
+    pub notification_repository: Arc<dyn NotificationRepository>,
+}
+
+#[derive(Clone, Debug)]
+pub struct PublishNotificationInput {
+    pub user_id: String,
+    pub title: String,
+    pub message: String,
+    pub url: Option<String>,
+}
+
+pub type PublishNotificationOutput = Result<Notification, PublishNotificationFailure>;
+
+impl PublishNotification {
+    pub async fn publish_notification(
+        &self,
+        input: PublishNotificationInput,
+    ) -> PublishNotificationOutput {
+        let notification = Notification {
+            id: uuid::Uuid::new_v4().to_string(),
+            user_id: input.user_id,
+            title: input.title,
+            message: input.message,
+            url: input.url,
+            created_at: Utc::now(),
+            read_at: None,
+        };
+
+        self.notification_repository
+            .save(notification.clone())
+            .await
+            .map_err(|e| PublishNotificationFailure::Unknown(e.to_string()))?;
+
+        Ok(notification)
+    }
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum PublishNotificationFailure {
+    #[error("Something went wrong")]
+    Unknown(String),
+}
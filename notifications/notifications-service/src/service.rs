@@ -0,0 +1,97 @@
+use std::sync::Arc;
+
+use crate::{
+    //##PLOP INSERT COMMAND IMPORTS HOOK##
+    count_unread_notifications::{
+        CountUnreadNotifications, CountUnreadNotificationsInput, CountUnreadNotificationsOutput,
+    },
+    get_notifications::{GetNotifications, GetNotificationsInput, GetNotificationsOutput},
+    mark_all_notifications_read::{
+        MarkAllNotificationsRead, MarkAllNotificationsReadInput, MarkAllNotificationsReadOutput,
+    },
+    mark_notification_read::{
+        MarkNotificationRead, MarkNotificationReadInput, MarkNotificationReadOutput,
+    },
+    ports::notification_repository::NotificationRepository,
+    publish_notification::{
+        PublishNotification, PublishNotificationInput, PublishNotificationOutput,
+    },
+};
+
+/**
+ * NotificationsService
+ *
+ * The in-app notification center. Other services publish notifications to a user,
+ * the web app lists them and marks them read.
+ */
+#[derive(Clone)]
+pub struct NotificationsService {
+    //##PLOP INSERT COMMAND HOOK##
+    pub publish_notification: PublishNotification,
+    pub get_notifications: GetNotifications,
+    pub count_unread_notifications: CountUnreadNotifications,
+    pub mark_notification_read: MarkNotificationRead,
+    pub mark_all_notifications_read: MarkAllNotificationsRead,
+}
+
+impl NotificationsService {
+    pub fn new(notification_repository: Arc<dyn NotificationRepository>) -> Self {
+        Self {
+            //##PLOP INSERT COMMAND INSTANTIATION HOOK##
+            publish_notification: PublishNotification {
+                notification_repository: notification_repository.clone(),
+            },
+            get_notifications: GetNotifications {
+                notification_repository: notification_repository.clone(),
+            },
+            count_unread_notifications: CountUnreadNotifications {
+                notification_repository: notification_repository.clone(),
+            },
+            mark_notification_read: MarkNotificationRead {
+                notification_repository: notification_repository.clone(),
+            },
+            mark_all_notifications_read: MarkAllNotificationsRead {
+                notification_repository,
+            },
+        }
+    }
+    //##PLOP INSERT DELEGATE HOOK##
+
+    pub async fn publish_notification(
+        &self,
+        input: PublishNotificationInput,
+    ) -> PublishNotificationOutput {
+        self.publish_notification.publish_notification(input).await
+    }
+
+    pub async fn get_notifications(&self, input: GetNotificationsInput) -> GetNotificationsOutput {
+        self.get_notifications.get_notifications(input).await
+    }
+
+    pub async fn count_unread_notifications(
+        &self,
+        input: CountUnreadNotificationsInput,
+    ) -> CountUnreadNotificationsOutput {
+        self.count_unread_notifications
+            .count_unread_notifications(input)
+            .await
+    }
+
+    pub async fn mark_notification_read(
+        &self,
+        input: MarkNotificationReadInput,
+    ) -> MarkNotificationReadOutput {
+        self.mark_notification_read
+            .mark_notification_read(input)
+            .await
+    }
+
+    pub async fn mark_all_notifications_read(
+        &self,
+        input: MarkAllNotificationsReadInput,
+    ) -> MarkAllNotificationsReadOutput {
+        self.mark_all_notifications_read
+            .mark_all_notifications_read(input)
+            .await
+    }
+}
@@ -0,0 +1,3 @@
+pub mod diesel_user_repository;
+mod records;
+mod schema;
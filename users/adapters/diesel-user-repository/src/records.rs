@@ -0,0 +1,35 @@
+use diesel::{
+    prelude::{Identifiable, Queryable},
+    AsChangeset, Insertable, Selectable,
+};
+use users_service::models::User;
+
+use crate::schema::users;
+
+#[derive(Queryable, Selectable, Insertable, AsChangeset, Identifiable, PartialEq)]
+#[diesel(table_name = users)]
+pub struct UserRecord {
+    pub id: String,
+    pub username: String,
+    pub password_hash: String,
+}
+
+impl From<User> for UserRecord {
+    fn from(user: User) -> Self {
+        Self {
+            id: user.id,
+            username: user.username,
+            password_hash: user.password_hash,
+        }
+    }
+}
+
+impl From<UserRecord> for User {
+    fn from(record: UserRecord) -> Self {
+        Self {
+            id: record.id,
+            username: record.username,
+            password_hash: record.password_hash,
+        }
+    }
+}
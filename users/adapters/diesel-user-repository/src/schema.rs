@@ -0,0 +1,8 @@
+// See migrations/.
+diesel::table! {
+    users (id) {
+        id -> Text,
+        username -> Text,
+        password_hash -> Text,
+    }
+}
@@ -0,0 +1,106 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::sync::RwLock;
+use users_service::models::User;
+use users_service::ports::user_repository::{RepositoryFailure, UserRepository};
+
+#[derive(Clone, Debug)]
+pub struct InMemoryUserRepository {
+    pub users: Arc<RwLock<Vec<User>>>,
+}
+
+impl InMemoryUserRepository {
+    pub fn empty() -> Self {
+        Self {
+            users: Arc::new(RwLock::new(vec![])),
+        }
+    }
+
+    pub fn with(users: Vec<User>) -> Self {
+        Self {
+            users: Arc::new(RwLock::new(users)),
+        }
+    }
+}
+
+#[async_trait]
+impl UserRepository for InMemoryUserRepository {
+    async fn get_user(&self, id: String) -> Result<Option<User>, RepositoryFailure> {
+        let users = self.users.read().await;
+        Ok(users.iter().find(|u| u.id == id).map(|u| u.to_owned()))
+    }
+
+    async fn get_user_by_username(
+        &self,
+        username: String,
+    ) -> Result<Option<User>, RepositoryFailure> {
+        let users = self.users.read().await;
+        Ok(users
+            .iter()
+            .find(|u| u.username == username)
+            .map(|u| u.to_owned()))
+    }
+
+    async fn save(&self, user: User) -> Result<(), RepositoryFailure> {
+        let mut users = self.users.write().await;
+
+        if users
+            .iter()
+            .any(|u| u.username == user.username && u.id != user.id)
+        {
+            return Err(RepositoryFailure::UsernameTaken);
+        }
+
+        users.retain(|u| u.id != user.id);
+        users.push(user);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_get_user_by_username() {
+        let ann = User::new("ann", "correct horse");
+        let repository = InMemoryUserRepository::with(vec![ann.clone()]);
+
+        assert_eq!(
+            repository.get_user_by_username("ann".into()).await,
+            Ok(Some(ann.clone()))
+        );
+        assert_eq!(repository.get_user(ann.id.clone()).await, Ok(Some(ann)));
+        assert_eq!(
+            repository.get_user_by_username("bob".into()).await,
+            Ok(None)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_save_replaces_the_user() {
+        let mut ann = User::new("ann", "correct horse");
+        let repository = InMemoryUserRepository::with(vec![ann.clone()]);
+
+        ann.username = "ann.smith".into();
+        repository.save(ann.clone()).await.unwrap();
+
+        assert_eq!(repository.users.read().await.len(), 1);
+        assert_eq!(
+            repository.get_user_by_username("ann.smith".into()).await,
+            Ok(Some(ann))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_usernames_are_unique() {
+        let repository = InMemoryUserRepository::with(vec![User::new("ann", "correct horse")]);
+
+        let result = repository.save(User::new("ann", "battery staple")).await;
+
+        assert_eq!(result, Err(RepositoryFailure::UsernameTaken));
+        assert_eq!(repository.users.read().await.len(), 1);
+    }
+}
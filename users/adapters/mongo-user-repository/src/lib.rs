@@ -0,0 +1,2 @@
+pub mod mongo_user_repository;
+mod records;
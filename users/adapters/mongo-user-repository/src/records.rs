@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+use users_service::models::User;
+
+#[derive(Serialize, Deserialize, PartialEq)]
+pub struct UserRecord {
+    #[serde(rename = "_id")]
+    pub id: String,
+    pub username: String,
+    pub password_hash: String,
+}
+
+impl From<User> for UserRecord {
+    fn from(user: User) -> Self {
+        Self {
+            id: user.id,
+            username: user.username,
+            password_hash: user.password_hash,
+        }
+    }
+}
+
+impl From<UserRecord> for User {
+    fn from(record: UserRecord) -> Self {
+        Self {
+            id: record.id,
+            username: record.username,
+            password_hash: record.password_hash,
+        }
+    }
+}
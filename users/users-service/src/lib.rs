@@ -0,0 +1,3 @@
+//##PLOP INSERT MOD HOOK##
+pub mod models;
+pub mod ports;
@@ -0,0 +1,56 @@
+use std::fmt;
+
+use uuid::Uuid;
+
+/**
+ * User
+ *
+ * Someone who can sign in. Only the hash of their password is kept (see password-auth).
+ */
+#[derive(Clone, PartialEq)]
+pub struct User {
+    pub id: String,
+    pub username: String,
+    pub password_hash: String,
+}
+
+impl User {
+    // Hashing is slow on purpose, keep it off the async runtime (ex: `spawn_blocking`).
+    pub fn new(username: &str, password: &str) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            username: username.into(),
+            password_hash: password_auth::generate_hash(password),
+        }
+    }
+
+    // Slow on purpose too, see `new`.
+    pub fn verify_password(&self, password: &str) -> bool {
+        password_auth::verify_password(password, &self.password_hash).is_ok()
+    }
+}
+
+// Keep password hashes out of the logs.
+impl fmt::Debug for User {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("User")
+            .field("id", &self.id)
+            .field("username", &self.username)
+            .field("password_hash", &"[redacted]")
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_password() {
+        let user = User::new("ann", "correct horse");
+
+        assert!(user.verify_password("correct horse"));
+        assert!(!user.verify_password("battery staple"));
+        assert!(!format!("{:?}", user).contains(&user.password_hash));
+    }
+}
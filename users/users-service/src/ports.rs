@@ -0,0 +1,2 @@
+//##PLOP INSERT MOD HOOK##
+pub mod user_repository;
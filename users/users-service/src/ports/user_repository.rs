@@ -0,0 +1,27 @@
+use async_trait::async_trait;
+use thiserror::Error;
+
+use crate::models::User;
+
+#[async_trait]
+pub trait UserRepository: Send + Sync + 'static {
+    async fn get_user(&self, id: String) -> Result<Option<User>, RepositoryFailure>;
+
+    // Usernames are unique, saving a user with someone else's fails with `UsernameTaken`.
+    async fn get_user_by_username(
+        &self,
+        username: String,
+    ) -> Result<Option<User>, RepositoryFailure>;
+
+    async fn save(&self, user: User) -> Result<(), RepositoryFailure>;
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum RepositoryFailure {
+    #[error("Failed to get connection from pool")]
+    FailedToGetConnectionFromPool,
+    #[error("Username is taken")]
+    UsernameTaken,
+    #[error("Something went wrong")]
+    Unknown(String),
+}
@@ -1,35 +1,100 @@
-use std::{env, fs, io::Write, process::Command};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use sha2::{Digest, Sha384};
+use std::{env, fs, io::Write, path::Path, process::Command};
+
+include!("src/vendor.rs");
+
+// Bundles in ./out that get a content-hashed copy, see `write_asset_manifest`.
+const HASHED_ASSETS: &[&str] = &["common.css", "common.js"];
+
+// Set to `1` (or `true`) to skip bun and build with the assets already in ./out.
+const PREBUILT_ENV: &str = "WEB_CLIENT_PREBUILT";
 
 fn main() {
+    println!("cargo:rerun-if-env-changed={}", PREBUILT_ENV);
+    println!("cargo:rerun-if-changed=package.json");
+    println!("cargo:rerun-if-changed=src/vendor.rs");
     println!("cargo:rerun-if-changed=src/client");
     println!("cargo:rerun-if-changed=../web-htmx/src");
 
-    println!("Building client scripts {:?}", std::time::SystemTime::now());
-
     let out_dir = env::var("OUT_DIR").unwrap();
-    let hash = hash_package_json();
 
-    let should_bun_install = match read_hash_from_out(&out_dir) {
-        Ok(old_hash) => old_hash != hash,
-        Err(_) => true,
+    if is_prebuilt() {
+        println!(
+            "cargo:warning={} is set, using the prebuilt client assets in web-client/out",
+            PREBUILT_ENV
+        );
+
+        // Only the prebuilt assets are watched, built ones are written by this script and would
+        // rerun it on every build.
+        let read_assets = VENDOR_SCRIPTS
+            .iter()
+            .map(|script| script.path())
+            .chain(HASHED_ASSETS.iter().map(|asset| asset.to_string()));
+        for asset in read_assets {
+            println!("cargo:rerun-if-changed=out/{}", asset);
+        }
+    } else {
+        println!("Building client scripts {:?}", std::time::SystemTime::now());
+
+        let hash = hash_package_json();
+        let should_bun_install = match read_hash_from_out(&out_dir) {
+            Ok(old_hash) => old_hash != hash,
+            Err(_) => true,
+        };
+
+        if should_bun_install {
+            exec_bun_install();
+            // Only once it succeeded, so a failed install is retried on the next build.
+            write_hash_to_out(&out_dir, &hash);
+        }
+
+        copy_vendor_scripts();
+        exec_build_js_and_css();
+        println!("Built client scripts {:?}", std::time::SystemTime::now());
+    }
+
+    write_vendor_integrity(&out_dir);
+    write_asset_manifest(&out_dir);
+
+    #[cfg(feature = "embed-assets")]
+    write_embedded_assets(&out_dir);
+}
+
+fn is_prebuilt() -> bool {
+    env::var(PREBUILT_ENV).is_ok_and(|value| value == "1" || value.eq_ignore_ascii_case("true"))
+}
+
+// Release and embedded builds are the ones that get deployed, missing assets fail them instead
+// of warning.
+fn ships_assets() -> bool {
+    cfg!(feature = "embed-assets") || env::var("PROFILE").is_ok_and(|profile| profile == "release")
+}
+
+// Runs `command` with sh and fails the build with its output when it fails, so we never build
+// a binary with stale or missing assets.
+fn exec(command: &str) {
+    let output = match Command::new("sh").args(["-c", command]).output() {
+        Ok(output) => output,
+        Err(e) => panic!("failed to execute `{}`: {}", command, e),
     };
 
-    if should_bun_install {
-        write_hash_to_out(&out_dir, &hash);
-        exec_bun_install();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    if !output.status.success() {
+        panic!(
+            "`{}` failed ({})\n\n{}{}\nIs bun installed (https://bun.sh)? To build with the assets already in web-client/out, set {}=1.",
+            command, output.status, stdout, stderr, PREBUILT_ENV
+        );
     }
 
-    exec_build_js_and_css();
-    println!("Built client scripts {:?}", std::time::SystemTime::now());
+    // Only shown with `cargo build -vv`.
+    println!("{}{}", stdout, stderr);
 }
 
 fn exec_bun_install() {
-    let output = Command::new("sh")
-        .args(["-c", "bun install"])
-        .output()
-        .expect("failed to execute `bun install`");
-
-    println!("{:?}", output);
+    exec("bun install");
 }
 
 fn exec_build_js_and_css() {
@@ -46,14 +111,214 @@ fn exec_build_js_and_css() {
         "bun build ./src/client/common.js --outdir ./out --sourcemap=external {}",
         minify_flag
     );
-    let build_cmd = format!("{} && {}", css_build.trim(), js_build.trim());
 
-    let output = Command::new("sh")
-        .args(["-c", build_cmd.as_str()])
-        .output()
-        .expect("failed to execute tailwind and bun build process");
+    exec(css_build.trim());
+    exec(js_build.trim());
+}
+
+// Copies the vendored scripts out of node_modules into ./out/vendor.
+fn copy_vendor_scripts() {
+    for script in VENDOR_SCRIPTS {
+        let package_dir = format!("node_modules/{}", script.package);
+
+        match installed_version(&package_dir) {
+            Some(version) if version == script.version => {}
+            installed => panic!(
+                "expected {}@{} in node_modules, found {:?}. Run `bun install` and check package.json agrees with src/vendor.rs.",
+                script.package, script.version, installed
+            ),
+        }
+
+        let destination = format!("out/{}", script.path());
+        fs::create_dir_all(Path::new(&destination).parent().unwrap()).unwrap();
+        fs::copy(format!("{}/{}", package_dir, script.file), &destination).unwrap();
+    }
+}
+
+// Integrity hashes of the vendored scripts in ./out/vendor, for `server::vendor_scripts`.
+fn write_vendor_integrity(out_dir: &str) {
+    let mut integrity = vec![];
+
+    for script in VENDOR_SCRIPTS {
+        let bytes = match fs::read(format!("out/{}", script.path())) {
+            Ok(bytes) => bytes,
+            Err(e) if ships_assets() => panic!(
+                "unable to read out/{}: {}\nPages would load it without its integrity hash. Build the client (unset {}) or add it to web-client/out.",
+                script.path(),
+                e,
+                PREBUILT_ENV
+            ),
+            Err(e) => {
+                println!("cargo:warning=Unable to read out/{}: {}", script.path(), e);
+                continue;
+            }
+        };
+
+        let hash = BASE64.encode(Sha384::digest(&bytes));
+        integrity.push(format!("    ({:?}, \"sha384-{}\"),", script.path(), hash));
+    }
+
+    let file_path = format!("{}/vendor_integrity.rs", out_dir);
+    let mut file = fs::File::create(file_path).unwrap();
+    writeln!(file, "pub const VENDOR_INTEGRITY: &[(&str, &str)] = &[").unwrap();
+    writeln!(file, "{}", integrity.join("\n")).unwrap();
+    writeln!(file, "];").unwrap();
+}
+
+fn installed_version(package_dir: &str) -> Option<String> {
+    let package_json = fs::read_to_string(format!("{}/package.json", package_dir)).ok()?;
+    let package_json: serde_json::Value = serde_json::from_str(&package_json).ok()?;
+
+    package_json["version"].as_str().map(String::from)
+}
 
-    println!("{:?}", output);
+// Copies each bundle to `<name>.<hash>.<ext>` and writes the name => hashed name manifest for
+// `web_client::assets`. Copies from previous builds are removed.
+fn write_asset_manifest(out_dir: &str) {
+    let mut manifest = vec![];
+
+    for asset in HASHED_ASSETS {
+        let (stem, extension) = asset.rsplit_once('.').unwrap();
+        remove_hashed_copies(stem, extension);
+
+        let bytes = match fs::read(format!("out/{}", asset)) {
+            Ok(bytes) => bytes,
+            Err(e) if ships_assets() => panic!(
+                "unable to read out/{}: {}\nPages would link it without its content hash. Build the client (unset {}) or add it to web-client/out.",
+                asset,
+                e,
+                PREBUILT_ENV
+            ),
+            Err(e) => {
+                println!("cargo:warning=Unable to read out/{}: {}", asset, e);
+                continue;
+            }
+        };
+
+        let hash = &sha256::digest(bytes.as_slice())[..16];
+        let hashed_asset = format!("{}.{}.{}", stem, hash, extension);
+        fs::write(format!("out/{}", hashed_asset), &bytes).unwrap();
+
+        manifest.push(format!("    ({:?}, {:?}),", asset, hashed_asset));
+    }
+
+    let file_path = format!("{}/asset_manifest.rs", out_dir);
+    let mut file = fs::File::create(file_path).unwrap();
+    writeln!(file, "pub const ASSET_MANIFEST: &[(&str, &str)] = &[").unwrap();
+    writeln!(file, "{}", manifest.join("\n")).unwrap();
+    writeln!(file, "];").unwrap();
+}
+
+fn remove_hashed_copies(stem: &str, extension: &str) {
+    let Ok(entries) = fs::read_dir("out") else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let is_hashed_copy = name
+            .strip_prefix(&format!("{}.", stem))
+            .and_then(|rest| rest.strip_suffix(&format!(".{}", extension)))
+            .is_some_and(|hash| hash.len() == 16 && hash.chars().all(|c| c.is_ascii_hexdigit()));
+
+        if is_hashed_copy {
+            fs::remove_file(entry.path()).unwrap();
+        }
+    }
+}
+
+// Writes the table of `web_client::embedded_assets`: every file in ./out, with its gzip and
+// brotli variants (when they're smaller).
+#[cfg(feature = "embed-assets")]
+fn write_embedded_assets(out_dir: &str) {
+    let client_out = env::current_dir().unwrap().join("out");
+    let compressed_dir = Path::new(out_dir).join("embedded");
+    let mut files = vec![];
+    collect_files(&client_out, &mut files);
+    files.sort();
+    assert!(
+        !files.is_empty(),
+        "nothing to embed, {:?} is empty",
+        client_out
+    );
+
+    let mut assets = vec![];
+
+    for file in files {
+        let bytes = fs::read(&file).unwrap();
+        let path = file
+            .strip_prefix(&client_out)
+            .unwrap()
+            .to_string_lossy()
+            .replace('\\', "/");
+        let content_type = mime_guess::from_path(&file).first_or_octet_stream();
+        let etag = format!("\"{}\"", &sha256::digest(bytes.as_slice())[..16]);
+
+        let compressed = |extension: &str, compress: fn(&[u8]) -> Vec<u8>| {
+            let compressed = compress(&bytes);
+            if compressed.len() >= bytes.len() {
+                return "None".to_string();
+            }
+
+            let compressed_file = compressed_dir.join(format!("{}.{}", path, extension));
+            fs::create_dir_all(compressed_file.parent().unwrap()).unwrap();
+            fs::write(&compressed_file, compressed).unwrap();
+
+            format!("Some(include_bytes!({:?}))", compressed_file)
+        };
+
+        assets.push(format!(
+            "    EmbeddedAsset {{ path: {:?}, content_type: {:?}, etag: {:?}, identity: include_bytes!({:?}), gzip: {}, brotli: {} }},",
+            path,
+            content_type.to_string(),
+            etag,
+            file,
+            compressed("gz", gzip),
+            compressed("br", brotli),
+        ));
+    }
+
+    let file_path = format!("{}/embedded_assets.rs", out_dir);
+    let mut file = fs::File::create(file_path).unwrap();
+    writeln!(file, "pub static EMBEDDED_ASSETS: &[EmbeddedAsset] = &[").unwrap();
+    writeln!(file, "{}", assets.join("\n")).unwrap();
+    writeln!(file, "];").unwrap();
+}
+
+#[cfg(feature = "embed-assets")]
+fn collect_files(dir: &Path, files: &mut Vec<std::path::PathBuf>) {
+    let entries = fs::read_dir(dir).unwrap_or_else(|e| {
+        panic!(
+            "unable to read {:?}: {}\nembed-assets builds serve their client assets from the binary. Build the client (unset {}) or add it to web-client/out.",
+            dir, e, PREBUILT_ENV
+        )
+    });
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+#[cfg(feature = "embed-assets")]
+fn gzip(bytes: &[u8]) -> Vec<u8> {
+    let mut encoder = flate2::write::GzEncoder::new(vec![], flate2::Compression::best());
+    encoder.write_all(bytes).unwrap();
+    encoder.finish().unwrap()
+}
+
+#[cfg(feature = "embed-assets")]
+fn brotli(bytes: &[u8]) -> Vec<u8> {
+    let mut compressed = vec![];
+    {
+        let mut encoder = brotli::CompressorWriter::new(&mut compressed, 4096, 11, 22);
+        encoder.write_all(bytes).unwrap();
+    }
+    compressed
 }
 
 fn read_hash_from_out(out_dir: &str) -> Result<String, std::io::Error> {
@@ -0,0 +1,76 @@
+use axum::{
+    body::Body,
+    http::{header::CACHE_CONTROL, HeaderValue, Request},
+    middleware::Next,
+    response::Response,
+};
+
+// `ASSET_MANIFEST: &[(name, hashed name)]`, generated by build.rs.
+include!(concat!(env!("OUT_DIR"), "/asset_manifest.rs"));
+
+const IMMUTABLE: &str = "public, max-age=31536000, immutable";
+const REVALIDATE: &str = "no-cache";
+
+/**
+ * asset_url
+ *
+ * The url of a client bundle (ex: `common.css`) by its content hash, so it can be cached
+ * forever and changes with its content.
+ *
+ * Falls back to the unhashed file when the bundle didn't build.
+ */
+pub fn asset_url(name: &str) -> String {
+    let file = ASSET_MANIFEST
+        .iter()
+        .find(|(asset, _)| *asset == name)
+        .map(|(_, hashed_asset)| *hashed_asset)
+        .unwrap_or(name);
+
+    format!("/client/{}", file)
+}
+
+// Hashed bundles and versioned vendor scripts never change.
+fn is_immutable(path: &str) -> bool {
+    let path = path.trim_start_matches('/');
+
+    path.starts_with("vendor/")
+        || ASSET_MANIFEST
+            .iter()
+            .any(|(_, hashed_asset)| *hashed_asset == path)
+}
+
+// Sets `Cache-Control` on responses served from ./out, expects paths relative to `/client`.
+pub async fn cache_control_layer(request: Request<Body>, next: Next) -> Response {
+    let immutable = is_immutable(request.uri().path());
+    let mut response = next.run(request).await;
+
+    if response.status().is_success() {
+        let value = if immutable { IMMUTABLE } else { REVALIDATE };
+        response
+            .headers_mut()
+            .insert(CACHE_CONTROL, HeaderValue::from_static(value));
+    }
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_asset_url() {
+        let url = asset_url("common.css");
+
+        assert!(url.starts_with("/client/common."));
+        assert!(url.ends_with(".css"));
+        assert_eq!(asset_url("other.css"), "/client/other.css");
+    }
+
+    #[test]
+    fn test_versioned_files_are_immutable() {
+        assert!(is_immutable("/vendor/htmx.org@1.9.9/dist/htmx.min.js"));
+        assert!(!is_immutable("/common.css"));
+        assert!(!is_immutable("/common.js.map"));
+    }
+}
@@ -0,0 +1,36 @@
+import { ControlRegistry } from "./registery";
+
+const global = (window as any);
+
+/**
+ * Calls a `YcControls` function when an element with `data-yc-click` is clicked, the CSP blocks
+ * inline `onclick` handlers. `data-yc-args` is a json array of arguments (see `yc_click` in
+ * yc_control.rs):
+ *
+ * <button data-yc-click="showSuccessNotification" data-yc-args='["Saved!"]'>
+ */
+function onClick(event: Event) {
+  const element = (event.target as Element | null)?.closest<HTMLElement>("[data-yc-click]");
+  if (!element) return;
+
+  const name = element.dataset.ycClick!;
+  const fn = global.YcControls?.[name];
+  if (typeof fn !== "function") {
+    console.warn(`No YcControls function named: ${name}. Skipping.`);
+    return;
+  }
+
+  fn(...JSON.parse(element.dataset.ycArgs || "[]"));
+}
+
+function init(registry: ControlRegistry) {
+  document.addEventListener("click", onClick);
+
+  registry.registerGlobalApi({
+    historyBack: () => history.back(),
+  });
+}
+
+export default {
+  init,
+}
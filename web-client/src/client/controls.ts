@@ -1,5 +1,6 @@
 import registry from "./registery";
 import events from "./events";
+import clicks from "./clicks";
 import Notifications from "./controls/Notification";
 import Toggle from "./controls/Toggle";
 import Modal from "./controls/Modal";
@@ -43,6 +44,7 @@ function init() {
   const YcControls = global.YcControls = create(global.YcControls.attachOnReadyQueue, global.YcControls.onReadyQueue);
 
   events.init(registry);
+  clicks.init(registry);
   Notifications.init(registry);
   Toggle.init(registry);
   Modal.init(registry);
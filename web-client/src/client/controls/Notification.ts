@@ -1,11 +1,15 @@
 import events from "../events";
 import query from "../query";
 import { ControlRegistry } from "../registery";
+import { DEFAULT_TIMEOUT, NotificationAction, NotificationOptions, errorOptions } from "../notificationOptions";
 import Toggle from "./Toggle";
 
+declare const htmx: { process: (element: Element) => void };
+
 type NotificationEventDetails
-  = { kind: 'SUCCESS' | 'ERROR' | 'GENERIC' }
-  & Partial<NotificationCommand>;
+  = { kind: 'SUCCESS' | 'ERROR' | 'WARNING' | 'INFO' | 'DEBUG' | 'GENERIC' }
+  & Partial<NotificationCommand>
+  & NotificationOptions;
 
 type NotificationCommand = {
   title: string,
@@ -17,7 +21,10 @@ type RenderTemplateDelegate = {
   notificationWillAppend?: (notificationEl: HTMLElement) => void,
 };
 
-function renderStandardTemplate({ title, message, ...delegate }: NotificationCommand & RenderTemplateDelegate) {
+function renderStandardTemplate(
+  { title, message, ...delegate }: NotificationCommand & RenderTemplateDelegate,
+  options: NotificationOptions = {},
+) {
   const tpl = query(document, "#tpl-notification") as HTMLTemplateElement;
   const notification = tpl.content.cloneNode(true) as HTMLElement;
 
@@ -34,11 +41,13 @@ function renderStandardTemplate({ title, message, ...delegate }: NotificationCom
     elements.defaultIcon.replaceWith(delegate.iconElement);
   }
 
+  renderActions(notification, options.actions ?? []);
+
   delegate.notificationWillAppend?.(notification);
-  return Notifications.appendNotification(notification);
+  return Notifications.appendNotification(notification, options);
 };
 
-function renderCustomTemplate(templateSelector: string | HTMLTemplateElement) {
+function renderCustomTemplate(templateSelector: string | HTMLTemplateElement, options: NotificationOptions = {}) {
   const tpl = (() => {
     if (typeof templateSelector === "string") {
       return query(document, templateSelector) as HTMLTemplateElement;
@@ -55,63 +64,158 @@ function renderCustomTemplate(templateSelector: string | HTMLTemplateElement) {
   // Ensure notification is clickable (live-region disables pointer events)
   notification.firstElementChild?.classList.add("pointer-events-auto");
 
-  return Notifications.appendNotification(notification);
+  // Custom templates can opt in to actions with a `[data-notification-actions]` element.
+  renderActions(notification, options.actions ?? []);
+
+  return Notifications.appendNotification(notification, options);
+}
+
+function renderActions(notification: HTMLElement, actions: NotificationAction[]) {
+  const actionsElement = notification.querySelector("[data-notification-actions]");
+  if (!actionsElement) {
+    if (actions.length > 0) {
+      console.warn("Notification template has no [data-notification-actions] element, actions are ignored.");
+    }
+    return;
+  }
+
+  actions.forEach(({ label, hx }) => {
+    const tpl = query(document, "#tpl-notification-action") as HTMLTemplateElement;
+    const button = (tpl.content.cloneNode(true) as HTMLElement).firstElementChild!;
+
+    button.textContent = label;
+    Object.entries(hx).forEach(([key, value]) => button.setAttribute(key, value));
+    actionsElement.appendChild(button);
+  });
 }
 
-function iconFromTemplate(iconKey: "success" | "error" | "info") {
+function iconFromTemplate(iconKey: "success" | "error" | "info" | "warning" | "debug") {
   const tpl = query(document, "#tpl-notification-icons") as HTMLTemplateElement;
   const iconsTemplate = tpl.content.cloneNode(true) as HTMLElement;
 
   return query(iconsTemplate, `[data-notification-icon=${iconKey}]`);
 }
 
+// htmx wraps non-object `HX-Trigger` payloads (ex: a list of notifications) in `{ value }`.
+type NotificationRequest = NotificationEventDetails | { value: NotificationEventDetails[] };
+
+function showRequest(request: NotificationEventDetails) {
+  const { title = "Notification", message = "", kind, ...options } = request;
+  switch (kind) {
+    case "SUCCESS":
+      Notifications.showSuccess(message, options);
+      break;
+    case "ERROR":
+      Notifications.showError(message, options);
+      break;
+    case "WARNING":
+      Notifications.showWarning(message, options);
+      break;
+    case "INFO":
+      Notifications.showInfo(message, options);
+      break;
+    case "DEBUG":
+      Notifications.showDebug(message, options);
+      break;
+    case "GENERIC":
+      Notifications.show(title, message, options);
+      break;
+  }
+}
+
+function showRequests(request: NotificationRequest) {
+  const requests = "value" in request ? request.value : [request];
+  requests.forEach(showRequest);
+}
+
 const Notifications = {
   init() {
-    events.on("yc:notificationRequest", (request: NotificationEventDetails) => {
-      const { title = "Notification", message = "", } = request;
-      switch (request.kind) {
-        case "SUCCESS":
-          Notifications.showSuccess(message);
-          break;
-        case "ERROR":
-          Notifications.showError(message);
-          break;
-        case "GENERIC":
-          Notifications.show(title, message);
-          break;
-      }
+    events.on("yc:notificationRequest", (request: NotificationRequest, event) => {
+      event.stopPropagation();
+      showRequests(request);
+    });
+
+    // Sent by the server in an `HX-Trigger` header. htmx dispatches these on the element that made
+    // the request, which isn't always inside of the app element (ex: modals).
+    document.addEventListener("yc:notificationRequest", (event) => {
+      showRequests((event as CustomEvent<NotificationRequest>).detail);
     });
   },
 
-  async appendNotification(notification: HTMLElement) {
+  async appendNotification(notification: HTMLElement, options: NotificationOptions = {}) {
     // Fragments don't provide a reference to DOM element, first child is actual element attached.
-    const notificationElement = notification.firstElementChild!;
+    const notificationElement = notification.firstElementChild! as HTMLElement;
     const contentElement = query(document, "#notification-live-region [data-notification-content]");
 
-    const toggle = Toggle.attach(notificationElement as HTMLElement, {
+    const toggle = Toggle.attach(notificationElement, {
       toggleWillOpen: () => contentElement.appendChild(notification),
-      toggleClosed: () => contentElement.removeChild(notificationElement),
+      // Closing can race with the timeout, only remove once.
+      toggleClosed: () => notificationElement.remove(),
       shouldToggleCloseOnBodyClick: false,
     });
 
-    return await toggle.open();
+    // Actions close the notification once the server has answered.
+    notificationElement.querySelectorAll("[data-notification-action]").forEach((actionElement) => {
+      actionElement.addEventListener("htmx:afterRequest", () => toggle.close());
+    });
+
+    await toggle.open();
+
+    // Cloned template content hasn't been seen by htmx yet.
+    htmx.process(notificationElement);
+
+    if (!options.sticky) {
+      autoDismiss(notificationElement, options.timeout ?? DEFAULT_TIMEOUT, () => toggle.close());
+    }
   },
 
-  show: (title: string, message: string) => renderStandardTemplate({ title, message }),
+  show: (title: string, message: string, options?: NotificationOptions) => renderStandardTemplate(
+    { title, message },
+    options,
+  ),
 
-  showSuccess: (message: string) => renderStandardTemplate({
+  showSuccess: (message: string, options?: NotificationOptions) => renderStandardTemplate({
     title: "Success!",
     message,
     iconElement: iconFromTemplate("success"),
-  }),
+  }, options),
 
-  showError: (message: string) => renderStandardTemplate({
+  showError: (message: string, options?: NotificationOptions) => renderStandardTemplate({
     title: "Oops! Something went wrong",
     message,
     iconElement: iconFromTemplate("error"),
-  }),
+  }, errorOptions(options)),
+
+  showWarning: (message: string, options?: NotificationOptions) => renderStandardTemplate({
+    title: "Warning",
+    message,
+    iconElement: iconFromTemplate("warning"),
+  }, options),
+
+  showInfo: (message: string, options?: NotificationOptions) => renderStandardTemplate({
+    title: "For your information",
+    message,
+    iconElement: iconFromTemplate("info"),
+  }, options),
+
+  showDebug: (message: string, options?: NotificationOptions) => renderStandardTemplate({
+    title: "Debug",
+    message,
+    iconElement: iconFromTemplate("debug"),
+  }, options),
 };
 
+// Closes after `timeout`. The countdown restarts while the pointer is over the notification.
+function autoDismiss(element: HTMLElement, timeout: number, close: () => void) {
+  let timer = setTimeout(close, timeout);
+
+  element.addEventListener("mouseenter", () => clearTimeout(timer));
+  element.addEventListener("mouseleave", () => {
+    clearTimeout(timer);
+    timer = setTimeout(close, timeout);
+  });
+}
+
 function init(registry: ControlRegistry) {
   Notifications.init();
 
@@ -119,6 +223,9 @@ function init(registry: ControlRegistry) {
     showNotification: Notifications.show,
     showSuccessNotification: Notifications.showSuccess,
     showErrorNotification: Notifications.showError,
+    showWarningNotification: Notifications.showWarning,
+    showInfoNotification: Notifications.showInfo,
+    showDebugNotification: Notifications.showDebug,
     showNotificationWithTemplate: renderCustomTemplate,
   });
 }
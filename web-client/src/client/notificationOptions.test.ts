@@ -0,0 +1,13 @@
+import { describe, expect, test } from "bun:test";
+import { errorOptions } from "./notificationOptions";
+
+describe("errorOptions", () => {
+  test("keeps errors open by default", () => {
+    expect(errorOptions().sticky).toBe(true);
+    expect(errorOptions({ actions: [] }).sticky).toBe(true);
+  });
+
+  test("closes errors after a given timeout", () => {
+    expect(errorOptions({ timeout: 3000 })).toEqual({ sticky: false, timeout: 3000 });
+  });
+});
@@ -0,0 +1,19 @@
+// See `NotificationOptions` in notification.rs
+export type NotificationOptions = {
+  actions?: NotificationAction[],
+  timeout?: number, // milliseconds
+  sticky?: boolean,
+}
+
+export type NotificationAction = {
+  label: string,
+  hx: Record<string, string>,
+}
+
+export const DEFAULT_TIMEOUT = 5000;
+
+// Errors stay open until they're closed, they're easy to miss otherwise. A timeout still applies
+// when one is given.
+export function errorOptions(options: NotificationOptions = {}): NotificationOptions {
+  return { sticky: options.timeout == null, ...options };
+}
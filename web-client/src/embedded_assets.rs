@@ -0,0 +1,144 @@
+use axum::{
+    body::Body,
+    http::{
+        header::{ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_TYPE, ETAG, IF_NONE_MATCH, VARY},
+        HeaderMap, StatusCode, Uri,
+    },
+    response::{IntoResponse, Response},
+};
+
+/**
+ * EmbeddedAsset
+ *
+ * A file from ./out, compiled into the binary with the `embed-assets` feature. The table
+ * (`EMBEDDED_ASSETS`) is generated by build.rs, with precompressed variants of each file.
+ */
+pub struct EmbeddedAsset {
+    pub path: &'static str,
+    pub content_type: &'static str,
+    pub etag: &'static str,
+    pub identity: &'static [u8],
+    pub gzip: Option<&'static [u8]>,
+    pub brotli: Option<&'static [u8]>,
+}
+
+include!(concat!(env!("OUT_DIR"), "/embedded_assets.rs"));
+
+// Fallback handler of `web_client::routes`, expects paths relative to `/client`.
+pub async fn serve_embedded_asset(uri: Uri, headers: HeaderMap) -> Response {
+    let path = uri.path().trim_start_matches('/');
+
+    match EMBEDDED_ASSETS.iter().find(|asset| asset.path == path) {
+        Some(asset) => asset_response(asset, &headers),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+fn asset_response(asset: &EmbeddedAsset, headers: &HeaderMap) -> Response {
+    if etag_matches(asset.etag, headers) {
+        return (StatusCode::NOT_MODIFIED, [(ETAG, asset.etag)]).into_response();
+    }
+
+    let (encoding, bytes) = match (asset.brotli, asset.gzip) {
+        (Some(bytes), _) if accepts_encoding(headers, "br") => (Some("br"), bytes),
+        (_, Some(bytes)) if accepts_encoding(headers, "gzip") => (Some("gzip"), bytes),
+        _ => (None, asset.identity),
+    };
+
+    let mut response = (
+        [
+            (CONTENT_TYPE, asset.content_type),
+            (ETAG, asset.etag),
+            (VARY, ACCEPT_ENCODING.as_str()),
+        ],
+        Body::from(bytes),
+    )
+        .into_response();
+
+    if let Some(encoding) = encoding {
+        response
+            .headers_mut()
+            .insert(CONTENT_ENCODING, encoding.parse().unwrap());
+    }
+
+    response
+}
+
+fn etag_matches(etag: &str, headers: &HeaderMap) -> bool {
+    headers
+        .get_all(IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|tag| tag.trim().trim_start_matches("W/"))
+        .any(|tag| tag == "*" || tag == etag)
+}
+
+// `Accept-Encoding: gzip, br;q=0.8`, an encoding with `q=0` is refused.
+fn accepts_encoding(headers: &HeaderMap, encoding: &str) -> bool {
+    headers
+        .get_all(ACCEPT_ENCODING)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|value| {
+            let mut parts = value.split(';').map(str::trim);
+            let name = parts.next().unwrap_or_default();
+            let refused = parts.any(|param| {
+                param
+                    .strip_prefix("q=")
+                    .and_then(|q| q.parse::<f32>().ok())
+                    .is_some_and(|q| q == 0.0)
+            });
+
+            name.eq_ignore_ascii_case(encoding) && !refused
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static ASSET: EmbeddedAsset = EmbeddedAsset {
+        path: "common.css",
+        content_type: "text/css",
+        etag: "\"0123456789abcdef\"",
+        identity: b"body { color: red; }",
+        gzip: Some(b"gzipped"),
+        brotli: None,
+    };
+
+    fn headers(pairs: &[(&str, &str)]) -> HeaderMap {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.parse().unwrap(), value.parse().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_serves_precompressed_variant() {
+        let response = asset_response(&ASSET, &headers(&[("accept-encoding", "br, gzip")]));
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_ENCODING], "gzip");
+        assert_eq!(response.headers()[CONTENT_TYPE], "text/css");
+        assert_eq!(response.headers()[VARY], "accept-encoding");
+    }
+
+    #[test]
+    fn test_serves_identity_when_encoding_is_refused() {
+        let response = asset_response(&ASSET, &headers(&[("accept-encoding", "gzip;q=0")]));
+
+        assert!(response.headers().get(CONTENT_ENCODING).is_none());
+    }
+
+    #[test]
+    fn test_not_modified() {
+        let response = asset_response(
+            &ASSET,
+            &headers(&[("if-none-match", "\"other\", \"0123456789abcdef\"")]),
+        );
+
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    }
+}
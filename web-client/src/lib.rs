@@ -1,25 +1,78 @@
 extern crate self as web_client; // Allows web_client crate to import from itself when expanding macros.
 #[doc(inline)]
 pub use crate::server::html_layout::HtmlLayout;
-use axum::Router;
-use tower_http::services::ServeDir;
+use axum::{middleware, Router};
+use std::collections::HashMap;
 
+use crate::server::attrs::Attrs;
+use crate::server::hx::Hx;
+
+pub mod assets;
+#[cfg(feature = "embed-assets")]
+pub mod embedded_assets;
 pub mod server;
+pub mod vendor;
 
+// Serves ./out, from the binary with the `embed-assets` feature.
 pub fn routes() -> Router {
-    Router::new().nest_service("/", ServeDir::new("web-client/out"))
+    #[cfg(feature = "embed-assets")]
+    let router = Router::new().fallback(embedded_assets::serve_embedded_asset);
+
+    #[cfg(not(feature = "embed-assets"))]
+    let router =
+        Router::new().nest_service("/", tower_http::services::ServeDir::new("web-client/out"));
+
+    router.layer(middleware::from_fn(assets::cache_control_layer))
 }
 
-pub fn concat_attribute(field_value: &str, attribute_value: Option<&String>) -> String {
+pub fn concat_attribute(field_value: &str, attribute_value: Option<String>) -> String {
     let mut values = vec![];
 
     if !field_value.is_empty() {
         values.push(field_value.trim());
     }
 
-    if let Some(value) = attribute_value {
-        values.push(value.trim());
+    if let Some(value) = attribute_value.as_deref() {
+        if !value.trim().is_empty() {
+            values.push(value.trim());
+        }
     }
 
     values.join(" ")
 }
+
+// Used by #[html_element] to collect its attribute fields. Attributes set with the `hx` builder
+// replace the matching `hx_*` fields.
+pub fn html_attr_fields(fields: Vec<(&str, String)>, hx: &Hx) -> Vec<(String, String)> {
+    let mut fields: Vec<(String, String)> = fields
+        .into_iter()
+        .filter(|(key, _)| hx.attr(key).is_none())
+        .map(|(key, value)| (key.to_string(), value))
+        .collect();
+
+    fields.extend(
+        hx.iter()
+            .map(|(key, value)| (key.to_string(), value.to_string())),
+    );
+
+    fields
+}
+
+// Used by #[html_element] and spread_attrs! to merge the attribute fields of a component with
+// the ones set through its `attrs`.
+pub fn html_attrs_to_hashmap(
+    fields: &[(String, String)],
+    attrs: &Attrs,
+) -> HashMap<String, String> {
+    let mut map = HashMap::new();
+    let mut keys = vec![];
+
+    for (key, value) in fields {
+        map.insert(key.to_string(), concat_attribute(value, attrs.get(key)));
+        keys.push(key.as_str());
+    }
+
+    map.extend(attrs.to_hashmap_excluding(keys));
+
+    map
+}
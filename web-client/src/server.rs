@@ -2,11 +2,15 @@ pub mod alert;
 pub mod attrs;
 pub mod button;
 pub mod card;
+pub mod class_list;
+pub mod csp;
+pub mod escape;
 pub mod flyout;
 pub mod form;
 pub mod headers;
 pub mod html_element;
 pub mod html_layout;
+pub mod hx;
 pub mod modal;
 pub mod notification;
 pub mod opt_attrs;
@@ -14,4 +18,5 @@ pub mod page_header;
 pub mod popup_menu;
 pub mod table;
 pub mod transition;
+pub mod vendor_scripts;
 pub mod yc_control;
@@ -1,5 +1,8 @@
 use rscx::{component, html, props};
 
+use super::class_list::ClassList;
+use super::escape::escape_text;
+
 pub enum AlertKind {
     Error,
     Info,
@@ -32,13 +35,13 @@ pub fn Alert(props: AlertProps) -> String {
     };
 
     html! {
-        <div class=format!("rounded-md {} p-4 {}", bg_color, props.class).trim()>
+        <div class=ClassList::from("rounded-md p-4").merge(bg_color).merge(&props.class).to_string()>
             <div class="flex">
                 <div class="flex-shrink-0">
                     <AlertIcon kind=props.kind />
                 </div>
                 <div class="ml-3">
-                    <h3 class=format!("text-sm font-medium {}", title_color)>{props.title}</h3>
+                    <h3 class=format!("text-sm font-medium {}", title_color)>{escape_text(&props.title)}</h3>
                     {if !props.children.is_empty() {
                         html! {
                             <div class=format!("mt-2 text-sm {}", description_color)>{props.children}</div>
@@ -1,32 +1,44 @@
 use std::collections::HashMap;
 
-use super::opt_attrs::opt_attrs;
+use super::class_list::ClassList;
+use super::opt_attrs::{is_boolean_attribute, opt_attrs};
 
-#[derive(Default)]
+const CLASS: &str = "class";
+
+/**
+ * Attrs
+ *
+ * A bag of html attributes to spread onto an element. Keys can be static (`"hx-get"`) or built
+ * at runtime (`format!("data-{}", field)`). The `class` attribute is kept as a `ClassList`, so
+ * setting classes more than once merges them (later tailwind utilities win).
+ */
+#[derive(Clone, Default)]
 pub struct Attrs {
-    values: HashMap<&'static str, String>,
-    omit: Vec<&'static str>,
+    values: HashMap<String, String>,
+    classes: ClassList,
+    omit: Vec<String>,
 }
 impl Attrs {
-    pub fn omit(&self, fields_to_omit: Vec<&'static str>) -> Self {
+    pub fn omit<K: Into<String>>(&self, fields_to_omit: Vec<K>) -> Self {
         Self {
-            values: self.values.clone(),
-            omit: fields_to_omit,
+            omit: fields_to_omit.into_iter().map(Into::into).collect(),
+            ..self.clone()
         }
     }
-    pub fn to_hashmap(&self) -> HashMap<&'static str, String> {
+    pub fn to_hashmap(&self) -> HashMap<String, String> {
         let mut hashmap = self.values.clone();
 
+        if !self.classes.is_empty() {
+            hashmap.insert(CLASS.to_string(), self.classes.to_string());
+        }
+
         for field in &self.omit {
             hashmap.remove(field);
         }
 
         hashmap
     }
-    pub fn to_hashmap_excluding(
-        &self,
-        exclude: Vec<&'static str>,
-    ) -> HashMap<&'static str, String> {
+    pub fn to_hashmap_excluding(&self, exclude: Vec<&str>) -> HashMap<String, String> {
         let mut hashmap = self.to_hashmap();
 
         for field in exclude {
@@ -35,52 +47,171 @@ impl Attrs {
 
         hashmap
     }
-    pub fn with(key: &'static str, value: String) -> Self {
-        Self {
-            values: HashMap::from([(key, value)]),
-            omit: vec![],
-        }
+    pub fn with<K: Into<String>>(key: K, value: String) -> Self {
+        Self::default().set(key, value)
     }
-    pub fn set(&self, key: &'static str, value: String) -> Self {
-        let mut values = self.values.clone();
-        values.insert(key, value);
-
-        Self {
-            values,
-            omit: self.omit.clone(),
-        }
+    pub fn set<K: Into<String>>(&self, key: K, value: String) -> Self {
+        let mut attrs = self.clone();
+        attrs.insert(key.into(), value);
+        attrs
     }
-    pub fn set_if(&self, key: &'static str, value: String, condition: bool) -> Self {
+    pub fn set_if<K: Into<String>>(&self, key: K, value: String, condition: bool) -> Self {
         if condition {
             self.set(key, value)
         } else {
             self.clone()
         }
     }
-    pub fn get(&self, key: &'static str) -> Option<&String> {
-        if self.omit.contains(&key) {
+    // Boolean attributes (`disabled`, `checked`, `required`...) are rendered by name only when on.
+    pub fn set_bool<K: Into<String>>(&self, key: K, value: bool) -> Self {
+        let key = key.into();
+        let mut attrs = self.clone();
+
+        if value {
+            attrs.values.insert(key.clone(), key);
+        } else {
+            attrs.values.remove(&key);
+        }
+
+        attrs
+    }
+    pub fn get_bool(&self, key: &str) -> bool {
+        match self.get(key) {
+            Some(value) if is_boolean_attribute(key) => !matches!(value.trim(), "" | "false"),
+            Some(value) => !value.is_empty(),
+            None => false,
+        }
+    }
+    // Merges classes into the class list. Conflicting tailwind utilities are replaced.
+    pub fn add_class<S: AsRef<str>>(&self, classes: S) -> Self {
+        Self {
+            classes: self.classes.clone().merge(classes),
+            ..self.clone()
+        }
+    }
+    pub fn class_list(&self) -> &ClassList {
+        &self.classes
+    }
+    pub fn get(&self, key: &str) -> Option<String> {
+        if self.omit.iter().any(|omitted| omitted == key) {
             return None;
         }
-        self.values.get(key)
+
+        if key == CLASS {
+            return match self.classes.is_empty() {
+                true => None,
+                false => Some(self.classes.to_string()),
+            };
+        }
+
+        self.values.get(key).cloned()
+    }
+
+    fn insert(&mut self, key: String, value: String) {
+        if key == CLASS {
+            self.classes = ClassList::from(value);
+        } else {
+            self.values.insert(key, value);
+        }
     }
 }
 
-impl Clone for Attrs {
-    fn clone(&self) -> Self {
-        Self {
-            values: self.values.clone(),
-            omit: self.omit.clone(),
+impl From<HashMap<String, String>> for Attrs {
+    fn from(html_attrs: HashMap<String, String>) -> Self {
+        let mut attrs = Attrs::default();
+        for (key, value) in html_attrs {
+            attrs.insert(key, value);
         }
+        attrs
     }
 }
 
 impl From<HashMap<&'static str, String>> for Attrs {
     fn from(html_attrs: HashMap<&'static str, String>) -> Self {
+        let mut attrs = Attrs::default();
+        for (key, value) in html_attrs {
+            attrs.insert(key.to_string(), value);
+        }
+        attrs
+    }
+}
+
+/**
+ * SpreadAttrs
+ *
+ * What spread_attrs! works with: the attribute fields of a #[html_element] component and the
+ * `attrs` it was given. Each pipe transformer maps to a method:
+ *
+ * - `omit(a, b)`: drops attribute fields. Values set through `attrs` are still passed along.
+ * - `pick(a, b)`: keeps only these attributes, from both the fields and `attrs`.
+ * - `rename(a => b)`: moves the value of `a` to `b`.
+ * - `default(a = expr)`: sets `a` when the caller left it empty.
+ */
+#[derive(Clone, Default)]
+pub struct SpreadAttrs {
+    fields: Vec<(String, String)>,
+    attrs: Attrs,
+}
+impl SpreadAttrs {
+    pub fn new(fields: &[(String, String)], attrs: &Attrs) -> Self {
         Self {
-            values: html_attrs,
-            omit: vec![],
+            fields: fields.to_vec(),
+            attrs: attrs.clone(),
         }
     }
+    pub fn omit(mut self, keys: &[&str]) -> Self {
+        self.fields.retain(|(key, _)| !keys.contains(&key.as_str()));
+        self
+    }
+    pub fn pick(mut self, keys: &[&str]) -> Self {
+        self.fields.retain(|(key, _)| keys.contains(&key.as_str()));
+
+        let mut attrs = self.attrs.to_hashmap();
+        attrs.retain(|key, _| keys.contains(&key.as_str()));
+        self.attrs = Attrs::from(attrs);
+
+        self
+    }
+    pub fn rename(mut self, from: &str, to: &str) -> Self {
+        let value = self.take_field(from);
+        self.take_field(to);
+        if let Some(value) = value {
+            self.fields.push((to.to_string(), value));
+        }
+
+        let mut attrs = self.attrs.to_hashmap();
+        if let Some(value) = attrs.remove(from) {
+            attrs.insert(to.to_string(), value);
+        }
+        self.attrs = Attrs::from(attrs);
+
+        self
+    }
+    pub fn with_default<V: Into<String>>(mut self, key: &str, value: V) -> Self {
+        let is_set = self
+            .fields
+            .iter()
+            .any(|(k, v)| *k == key && !v.trim().is_empty())
+            || self.attrs.get(key).is_some_and(|v| !v.trim().is_empty());
+
+        if !is_set {
+            self.take_field(key);
+            self.fields.push((key.to_string(), value.into()));
+        }
+
+        self
+    }
+
+    fn take_field(&mut self, key: &str) -> Option<String> {
+        let index = self.fields.iter().position(|(k, _)| *k == key)?;
+        Some(self.fields.remove(index).1)
+    }
+}
+
+impl From<SpreadAttrs> for Attrs {
+    fn from(spread: SpreadAttrs) -> Self {
+        Attrs::from(crate::html_attrs_to_hashmap(&spread.fields, &spread.attrs))
+    }
 }
 
 impl From<Attrs> for String {
@@ -88,3 +219,99 @@ impl From<Attrs> for String {
         opt_attrs(attrs.to_hashmap())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_runtime_keys() {
+        let field = "worker-id";
+        let attrs = Attrs::default()
+            .set(format!("data-{}", field), "42".into())
+            .set("aria-describedby", format!("{}-help", field));
+
+        assert_eq!(
+            String::from(attrs),
+            String::from("aria-describedby=\"worker-id-help\" data-worker-id=\"42\"")
+        );
+    }
+
+    #[test]
+    fn test_add_class_merges_class_list() {
+        let attrs = Attrs::with("class", "rounded-md px-2 py-1".into())
+            .add_class("px-4 rounded-md")
+            .add_class("shadow");
+
+        assert_eq!(
+            attrs.get("class"),
+            Some(String::from("py-1 px-4 rounded-md shadow"))
+        );
+    }
+
+    #[test]
+    fn test_omit_class() {
+        let attrs = Attrs::with("class", "px-2".into())
+            .set("id", "foo".into())
+            .omit(vec!["class"]);
+
+        assert_eq!(attrs.get("class"), None);
+        assert_eq!(String::from(attrs), String::from("id=\"foo\""));
+    }
+
+    fn spread() -> SpreadAttrs {
+        SpreadAttrs::new(
+            &[
+                ("id".into(), "".into()),
+                ("name".into(), "email".into()),
+                ("class".into(), "px-2".into()),
+            ],
+            &Attrs::with("class", "py-1".into()).set("data-id", "42".into()),
+        )
+    }
+
+    #[test]
+    fn test_spread_attrs_omit_keeps_attrs() {
+        let attrs = Attrs::from(spread().omit(&["class", "name"]));
+
+        assert_eq!(
+            String::from(attrs),
+            String::from("class=\"py-1\" data-id=\"42\"")
+        );
+    }
+
+    #[test]
+    fn test_spread_attrs_pick() {
+        let attrs = Attrs::from(spread().pick(&["class", "id"]));
+
+        assert_eq!(String::from(attrs), String::from("class=\"px-2 py-1\""));
+    }
+
+    #[test]
+    fn test_spread_attrs_rename() {
+        let attrs = Attrs::from(spread().rename("name", "id").rename("data-id", "data-key"));
+
+        assert_eq!(
+            String::from(attrs),
+            String::from("class=\"px-2 py-1\" data-key=\"42\" id=\"email\"")
+        );
+    }
+
+    #[test]
+    fn test_spread_attrs_default() {
+        let attrs = Attrs::from(
+            spread()
+                .with_default("id", "email-input")
+                .with_default("name", "ignored")
+                .with_default("data-id", "ignored")
+                .with_default("type", "email"),
+        );
+
+        assert_eq!(
+            String::from(attrs),
+            String::from(
+                "class=\"px-2 py-1\" data-id=\"42\" id=\"email-input\" name=\"email\" type=\"email\""
+            )
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use super::class_list::ClassList;
 use super::html_element::HtmlElement;
 use rscx::{component, html, props};
 use web_macros::*;
@@ -41,7 +42,7 @@ pub fn PrimaryButton(props: PrimaryButtonProps) -> String {
                     ButtonSize::Lg => "rounded-md bg-indigo-600 px-3 py-2 text-sm font-semibold text-white shadow-sm hover:bg-indigo-500 focus-visible:outline focus-visible:outline-2 focus-visible:outline-offset-2 focus-visible:outline-indigo-600",
                     ButtonSize::Xl => "rounded-md bg-indigo-600 px-3.5 py-2.5 text-sm font-semibold text-white shadow-sm hover:bg-indigo-500 focus-visible:outline focus-visible:outline-2 focus-visible:outline-offset-2 focus-visible:outline-indigo-600",
                 };
-                format!("{} {}", class, props.class).trim()
+                ClassList::from(class).merge(&props.class)
             }
             attrs=spread_attrs!(props | omit(class))
                 .set("type", "button".into())
@@ -82,7 +83,7 @@ pub fn SecondaryButton(props: SecondaryButtonProps) -> String {
                     ButtonSize::Lg => "rounded-md bg-white px-3 py-2 text-sm font-semibold text-gray-900 shadow-sm ring-1 ring-inset ring-gray-300 hover:bg-gray-50",
                     ButtonSize::Xl => "rounded-md bg-white px-3.5 py-2.5 text-sm font-semibold text-gray-900 shadow-sm ring-1 ring-inset ring-gray-300 hover:bg-gray-50",
                 };
-                format!("{} {}", class, props.class).trim()
+                ClassList::from(class).merge(&props.class)
             }
             attrs=spread_attrs!(props | omit(class))
                 .set("type", "button".into())
@@ -1,5 +1,7 @@
 use rscx::{component, html, props};
 
+use super::class_list::ClassList;
+
 #[props]
 pub struct CardProps {
     children: String,
@@ -14,7 +16,7 @@ pub struct CardProps {
 #[component]
 pub fn Card(props: CardProps) -> String {
     html! {
-        <div class=format!("overflow-hidden shadow ring-1 ring-black ring-opacity-5 sm:rounded-lg {}", props.class).trim()>
+        <div class=ClassList::from("overflow-hidden shadow ring-1 ring-black ring-opacity-5 sm:rounded-lg").merge(&props.class).to_string()>
             <CardContent padded=props.padded>
                 {props.children}
             </CardContent>
@@ -0,0 +1,493 @@
+use std::fmt;
+
+/**
+ * ClassList
+ *
+ * An ordered, de-duplicated list of css classes. Classes added later win:
+ * when a tailwind utility conflicts with one already in the list (ex: `px-2` then `px-4`), the
+ * earlier one is dropped. This lets callers override a component's default styling by passing
+ * their own `class`.
+ *
+ * Conflicts are only resolved between utilities with the same variants (`sm:`, `hover:`...),
+ * so `px-2 sm:px-4` are both kept. Classes we don't recognize are only de-duplicated.
+ */
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ClassList {
+    classes: Vec<String>,
+}
+
+impl ClassList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn add<S: AsRef<str>>(&mut self, classes: S) {
+        for class in classes.as_ref().split_whitespace() {
+            self.add_class(class);
+        }
+    }
+    pub fn merge<S: AsRef<str>>(mut self, classes: S) -> Self {
+        self.add(classes);
+        self
+    }
+    pub fn extend(mut self, other: &ClassList) -> Self {
+        for class in &other.classes {
+            self.add_class(class);
+        }
+        self
+    }
+    pub fn contains(&self, class: &str) -> bool {
+        self.classes.iter().any(|c| c == class)
+    }
+    pub fn is_empty(&self) -> bool {
+        self.classes.is_empty()
+    }
+    pub fn iter(&self) -> impl Iterator<Item = &String> {
+        self.classes.iter()
+    }
+
+    fn add_class(&mut self, class: &str) {
+        match UtilityKey::parse(class) {
+            Some(key) => {
+                self.classes
+                    .retain(|existing| match UtilityKey::parse(existing) {
+                        Some(existing_key) => !key.overrides(&existing_key),
+                        None => true,
+                    });
+                self.classes.push(class.to_string());
+            }
+            None => {
+                if !self.contains(class) {
+                    self.classes.push(class.to_string());
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for ClassList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.classes.join(" "))
+    }
+}
+
+impl From<&str> for ClassList {
+    fn from(classes: &str) -> Self {
+        ClassList::new().merge(classes)
+    }
+}
+
+impl From<&String> for ClassList {
+    fn from(classes: &String) -> Self {
+        ClassList::new().merge(classes)
+    }
+}
+
+impl From<String> for ClassList {
+    fn from(classes: String) -> Self {
+        ClassList::new().merge(classes)
+    }
+}
+
+impl From<ClassList> for String {
+    fn from(class_list: ClassList) -> Self {
+        class_list.to_string()
+    }
+}
+
+// Groups that also reset other groups when set. Ex: `p-4` replaces an earlier `px-2`.
+const CONFLICTING_GROUPS: [(&str, &[&str]); 14] = [
+    ("p", &["px", "py", "pt", "pr", "pb", "pl", "ps", "pe"]),
+    ("px", &["pr", "pl", "ps", "pe"]),
+    ("py", &["pt", "pb"]),
+    ("m", &["mx", "my", "mt", "mr", "mb", "ml", "ms", "me"]),
+    ("mx", &["mr", "ml", "ms", "me"]),
+    ("my", &["mt", "mb"]),
+    (
+        "inset",
+        &[
+            "inset-x", "inset-y", "top", "right", "bottom", "left", "start", "end",
+        ],
+    ),
+    ("inset-x", &["right", "left"]),
+    ("inset-y", &["top", "bottom"]),
+    ("gap", &["gap-x", "gap-y"]),
+    ("size", &["w", "h"]),
+    ("overflow", &["overflow-x", "overflow-y"]),
+    (
+        "rounded",
+        &[
+            "rounded-t",
+            "rounded-r",
+            "rounded-b",
+            "rounded-l",
+            "rounded-tl",
+            "rounded-tr",
+            "rounded-br",
+            "rounded-bl",
+        ],
+    ),
+    (
+        "border-w",
+        &[
+            "border-w-x",
+            "border-w-y",
+            "border-w-t",
+            "border-w-r",
+            "border-w-b",
+            "border-w-l",
+        ],
+    ),
+];
+
+// Utility prefixes whose value never changes which css property they set.
+// Longer prefixes must come before shorter ones that share a start (ex: `px` before `p`).
+const PREFIX_GROUPS: [&str; 73] = [
+    "inset-x",
+    "inset-y",
+    "inset",
+    "top",
+    "right",
+    "bottom",
+    "left",
+    "start",
+    "end",
+    "z",
+    "px",
+    "py",
+    "pt",
+    "pr",
+    "pb",
+    "pl",
+    "ps",
+    "pe",
+    "p",
+    "mx",
+    "my",
+    "mt",
+    "mr",
+    "mb",
+    "ml",
+    "ms",
+    "me",
+    "m",
+    "space-x",
+    "space-y",
+    "gap-x",
+    "gap-y",
+    "gap",
+    "min-w",
+    "max-w",
+    "w",
+    "min-h",
+    "max-h",
+    "h",
+    "size",
+    "overflow-x",
+    "overflow-y",
+    "overflow",
+    "grid-cols",
+    "grid-rows",
+    "col-span",
+    "col-start",
+    "col-end",
+    "row-span",
+    "row-start",
+    "row-end",
+    "items",
+    "justify-items",
+    "justify-self",
+    "justify",
+    "content",
+    "self",
+    "order",
+    "grow",
+    "shrink",
+    "basis",
+    "opacity",
+    "leading",
+    "tracking",
+    "cursor",
+    "whitespace",
+    "duration",
+    "ease",
+    "delay",
+    "origin",
+    "translate-x",
+    "translate-y",
+    "line-clamp",
+];
+
+const DISPLAYS: [&str; 14] = [
+    "block",
+    "inline-block",
+    "inline",
+    "flex",
+    "inline-flex",
+    "table",
+    "inline-table",
+    "grid",
+    "inline-grid",
+    "contents",
+    "list-item",
+    "hidden",
+    "flow-root",
+    "table-row",
+];
+const POSITIONS: [&str; 5] = ["static", "fixed", "absolute", "relative", "sticky"];
+const FONT_SIZES: [&str; 13] = [
+    "xs", "sm", "base", "lg", "xl", "2xl", "3xl", "4xl", "5xl", "6xl", "7xl", "8xl", "9xl",
+];
+const TEXT_ALIGNS: [&str; 6] = ["left", "center", "right", "justify", "start", "end"];
+const FONT_WEIGHTS: [&str; 9] = [
+    "thin",
+    "extralight",
+    "light",
+    "normal",
+    "medium",
+    "semibold",
+    "bold",
+    "extrabold",
+    "black",
+];
+const BORDER_STYLES: [&str; 6] = ["solid", "dashed", "dotted", "double", "hidden", "none"];
+const SHADOW_SIZES: [&str; 7] = ["sm", "md", "lg", "xl", "2xl", "inner", "none"];
+const SIDES: [&str; 10] = ["x", "y", "t", "r", "b", "l", "tl", "tr", "br", "bl"];
+
+#[derive(Debug, PartialEq)]
+struct UtilityKey {
+    // Variants (ex: `sm:hover:`) plus the `!` important modifier.
+    modifiers: String,
+    group: String,
+}
+
+impl UtilityKey {
+    fn parse(class: &str) -> Option<Self> {
+        let (variants, utility) = split_variants(class);
+        let (important, utility) = match utility.strip_prefix('!') {
+            Some(utility) => ("!", utility),
+            None => ("", utility),
+        };
+        let utility = utility.strip_prefix('-').unwrap_or(utility);
+
+        utility_group(utility).map(|group| UtilityKey {
+            modifiers: format!("{}{}", variants, important),
+            group,
+        })
+    }
+
+    fn overrides(&self, other: &UtilityKey) -> bool {
+        if self.modifiers != other.modifiers {
+            return false;
+        }
+
+        self.group == other.group
+            || CONFLICTING_GROUPS.iter().any(|(group, conflicts)| {
+                *group == self.group && conflicts.contains(&other.group.as_str())
+            })
+    }
+}
+
+// Splits `sm:hover:px-4` into (`sm:hover:`, `px-4`), ignoring `:` inside arbitrary values (`[...]`).
+fn split_variants(class: &str) -> (&str, &str) {
+    let mut depth = 0;
+    let mut split_at = 0;
+
+    for (i, c) in class.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            ':' if depth == 0 => split_at = i + 1,
+            _ => {}
+        }
+    }
+
+    class.split_at(split_at)
+}
+
+fn utility_group(utility: &str) -> Option<String> {
+    if DISPLAYS.contains(&utility) {
+        return Some("display".into());
+    }
+    if POSITIONS.contains(&utility) {
+        return Some("position".into());
+    }
+
+    if let Some(value) = value_of(utility, "text") {
+        return Some(
+            match value {
+                _ if FONT_SIZES.contains(&value) => "text-size",
+                _ if TEXT_ALIGNS.contains(&value) => "text-align",
+                _ => "text-color",
+            }
+            .into(),
+        );
+    }
+    if let Some(value) = value_of(utility, "font") {
+        return Some(
+            match value {
+                _ if FONT_WEIGHTS.contains(&value) => "font-weight",
+                _ => "font-family",
+            }
+            .into(),
+        );
+    }
+    if let Some(value) = value_of(utility, "bg") {
+        return Some(
+            match value {
+                _ if value.starts_with("opacity-") => "bg-opacity",
+                _ if value.starts_with("gradient-") => "bg-image",
+                _ => "bg-color",
+            }
+            .into(),
+        );
+    }
+    if utility == "border" || utility.starts_with("border-") {
+        return Some(border_group(
+            utility.trim_start_matches("border").trim_start_matches('-'),
+        ));
+    }
+    if utility == "ring" || utility.starts_with("ring-") {
+        let value = utility.trim_start_matches("ring").trim_start_matches('-');
+        return Some(match value {
+            "" => "ring-w".to_string(),
+            "inset" => "ring-inset".to_string(),
+            _ if value.starts_with("offset-") => "ring-offset".to_string(),
+            _ if value.starts_with("opacity-") => "ring-opacity".to_string(),
+            _ if is_width(value) => "ring-w".to_string(),
+            _ => "ring-color".to_string(),
+        });
+    }
+    if utility == "rounded" || utility.starts_with("rounded-") {
+        let value = utility
+            .trim_start_matches("rounded")
+            .trim_start_matches('-');
+        let side = value.split('-').next().unwrap_or_default();
+        return Some(match SIDES.contains(&side) {
+            true => format!("rounded-{}", side),
+            false => "rounded".to_string(),
+        });
+    }
+    if utility == "shadow" || utility.starts_with("shadow-") {
+        let value = utility.trim_start_matches("shadow").trim_start_matches('-');
+        return Some(
+            match value {
+                "" => "shadow",
+                _ if SHADOW_SIZES.contains(&value) => "shadow",
+                _ => "shadow-color",
+            }
+            .into(),
+        );
+    }
+
+    PREFIX_GROUPS
+        .iter()
+        .find(|prefix| value_of(utility, prefix).is_some())
+        .map(|prefix| prefix.to_string())
+}
+
+fn border_group(value: &str) -> String {
+    if value.is_empty() || is_width(value) {
+        return "border-w".into();
+    }
+    if BORDER_STYLES.contains(&value) {
+        return "border-style".into();
+    }
+    if value.starts_with("opacity-") {
+        return "border-opacity".into();
+    }
+
+    match value.split_once('-') {
+        Some((side, width)) if SIDES.contains(&side) && is_width(width) => {
+            format!("border-w-{}", side)
+        }
+        None if SIDES.contains(&value) => format!("border-w-{}", value),
+        _ => "border-color".into(),
+    }
+}
+
+fn is_width(value: &str) -> bool {
+    value.chars().all(|c| c.is_ascii_digit()) || value.starts_with('[')
+}
+
+// `px-4` with prefix `px` => Some("4"). `px` => Some(""). `pxl` => None.
+fn value_of<'a>(utility: &'a str, prefix: &str) -> Option<&'a str> {
+    match utility.strip_prefix(prefix) {
+        Some("") => Some(""),
+        Some(rest) => rest.strip_prefix('-'),
+        None => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_class_list_dedupes() {
+        let classes = ClassList::from("foo bar foo  baz bar");
+        assert_eq!(classes.to_string(), String::from("foo bar baz"));
+    }
+
+    #[test]
+    fn test_class_list_later_utility_wins() {
+        let classes = ClassList::from("rounded-md px-2 py-1 text-sm").merge("px-4");
+        assert_eq!(
+            classes.to_string(),
+            String::from("rounded-md py-1 text-sm px-4")
+        );
+    }
+
+    #[test]
+    fn test_class_list_keeps_different_variants() {
+        let classes = ClassList::from("px-2 sm:px-4 hover:bg-gray-50").merge("sm:px-6 bg-white");
+        assert_eq!(
+            classes.to_string(),
+            String::from("px-2 hover:bg-gray-50 sm:px-6 bg-white")
+        );
+    }
+
+    #[test]
+    fn test_class_list_shorthand_replaces_sides() {
+        let classes = ClassList::from("px-2 py-1 pt-4").merge("p-3");
+        assert_eq!(classes.to_string(), String::from("p-3"));
+
+        // but a side doesn't replace the shorthand
+        let classes = ClassList::from("p-3").merge("px-2");
+        assert_eq!(classes.to_string(), String::from("p-3 px-2"));
+    }
+
+    #[test]
+    fn test_class_list_text_utilities_do_not_conflict_with_each_other() {
+        let classes = ClassList::from("text-sm text-gray-900 text-left").merge("text-red-500");
+        assert_eq!(
+            classes.to_string(),
+            String::from("text-sm text-left text-red-500")
+        );
+    }
+
+    #[test]
+    fn test_class_list_border_and_ring() {
+        let classes = ClassList::from("border border-gray-300 ring-1 ring-gray-300")
+            .merge("border-2 ring-red-500");
+        assert_eq!(
+            classes.to_string(),
+            String::from("border-gray-300 ring-1 border-2 ring-red-500")
+        );
+    }
+
+    #[test]
+    fn test_class_list_display_and_position() {
+        let classes = ClassList::from("hidden relative").merge("flex absolute");
+        assert_eq!(classes.to_string(), String::from("flex absolute"));
+    }
+
+    #[test]
+    fn test_class_list_arbitrary_variants() {
+        let classes =
+            ClassList::from("data-[dragover]:border-2 border").merge("data-[dragover]:border-4");
+        assert_eq!(
+            classes.to_string(),
+            String::from("border data-[dragover]:border-4")
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use std::future::Future;
+
+use rscx::{component, props};
+
+use super::attrs::Attrs;
+
+/*
+ * Content-Security-Policy nonce
+ *
+ * Pages are served with `script-src 'nonce-...'` (see web-htmx's csp layer), so every `<script>`
+ * we render has to carry the nonce of the current request. Render scripts with `Script` and
+ * they pick it up on their own.
+ */
+
+tokio::task_local! {
+    static NONCE: String;
+}
+
+// Makes `nonce` available to components rendered while `f` runs.
+pub async fn provide_nonce<F: Future>(nonce: String, f: F) -> F::Output {
+    NONCE.scope(nonce, f).await
+}
+
+// The current request's nonce, if there is one.
+pub fn nonce() -> Option<String> {
+    NONCE.try_with(|nonce| nonce.clone()).ok()
+}
+
+/**
+ * Script
+ *
+ * A `<script>` with the current request's CSP nonce.
+ *
+ * <Script src="/client/common.js" />
+ * <Script>"console.log('inline scripts work too');"</Script>
+ */
+#[props]
+pub struct ScriptProps {
+    #[builder(setter(into), default)]
+    src: String,
+
+    #[builder(setter(into), default)]
+    integrity: String,
+
+    #[builder(setter(into), default)]
+    crossorigin: String,
+
+    #[builder(default)]
+    children: String,
+}
+
+#[component]
+pub fn Script(props: ScriptProps) -> String {
+    let nonce = nonce().unwrap_or_default();
+    let attrs = Attrs::default()
+        .set_if("src", props.src.clone(), !props.src.is_empty())
+        .set_if(
+            "integrity",
+            props.integrity.clone(),
+            !props.integrity.is_empty(),
+        )
+        .set_if(
+            "crossorigin",
+            props.crossorigin.clone(),
+            !props.crossorigin.is_empty(),
+        )
+        .set_if("nonce", nonce.clone(), !nonce.is_empty());
+
+    match String::from(attrs) {
+        attrs if attrs.is_empty() => format!("<script>{}</script>", props.children),
+        attrs => format!("<script {}>{}</script>", attrs, props.children),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rscx::html;
+
+    #[tokio::test]
+    async fn test_script_has_request_nonce() {
+        let html = provide_nonce("abc123".into(), async {
+            html! {
+                <Script src="/client/common.js" />
+            }
+        })
+        .await;
+
+        assert_eq!(
+            html,
+            r#"<script nonce="abc123" src="/client/common.js"></script>"#
+        );
+    }
+
+    #[tokio::test]
+    async fn test_inline_script_without_nonce() {
+        let html = html! {
+            <Script>"YcControls.attach(document.currentScript.parentElement);"</Script>
+        };
+
+        assert_eq!(
+            html,
+            "<script>YcControls.attach(document.currentScript.parentElement);</script>"
+        );
+    }
+}
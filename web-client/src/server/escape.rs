@@ -0,0 +1,76 @@
+use rscx::html_escape;
+use serde::Serialize;
+
+/*
+ * Escaping helpers
+ *
+ * Anything rendered by our components is raw markup (a `String` of html), so values that come
+ * from users or the database need to be escaped before they are placed on the page.
+ *
+ * - `escape_attribute`: for values placed inside a quoted attribute (`key="..."`).
+ * - `escape_text`: for plain text placed between tags (use this for titles, labels, messages...).
+ * - `escape_js`: for values handed to inline scripts or event handler attributes.
+ */
+
+pub fn escape_attribute<S: AsRef<str>>(value: S) -> String {
+    html_escape::encode_quoted_attribute(value.as_ref()).into_owned()
+}
+
+pub fn escape_text<S: AsRef<str>>(value: S) -> String {
+    html_escape::encode_text(value.as_ref()).into_owned()
+}
+
+// JSON encodes a value so it can be used as a js literal. `<`, `>` and `&` are unicode escaped
+// so the value can't close a surrounding `<script>` tag or start an html entity.
+pub fn escape_js<T>(data: &T) -> String
+where
+    T: ?Sized + Serialize,
+{
+    serde_json::to_string::<T>(data)
+        .unwrap()
+        .replace('<', "\\u003c")
+        .replace('>', "\\u003e")
+        .replace('&', "\\u0026")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_attribute_with_plain_string() {
+        assert_eq!(escape_attribute("foo bar-baz"), String::from("foo bar-baz"));
+    }
+
+    #[test]
+    fn test_escape_attribute_with_quotes() {
+        assert_eq!(
+            escape_attribute(r#"" onmouseover="alert('x')"#),
+            String::from("&quot; onmouseover=&quot;alert(&#x27;x&#x27;)")
+        );
+    }
+
+    #[test]
+    fn test_escape_attribute_with_ampersands_and_angle_brackets() {
+        assert_eq!(
+            escape_attribute("a < b && b > c"),
+            String::from("a &lt; b &amp;&amp; b &gt; c")
+        );
+    }
+
+    #[test]
+    fn test_escape_text() {
+        assert_eq!(
+            escape_text("<script>alert(\"Tom & Jerry\")</script>"),
+            String::from("&lt;script&gt;alert(\"Tom &amp; Jerry\")&lt;/script&gt;")
+        );
+    }
+
+    #[test]
+    fn test_escape_js_with_closing_script_tag() {
+        assert_eq!(
+            escape_js("</script><b>Tom & Jerry's</b>"),
+            String::from(r#""\u003c/script\u003e\u003cb\u003eTom \u0026 Jerry's\u003c/b\u003e""#)
+        );
+    }
+}
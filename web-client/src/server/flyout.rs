@@ -1,4 +1,5 @@
 use super::attrs::Attrs;
+use super::escape::escape_text;
 use super::transition::Transition;
 use super::yc_control::YcControl;
 use rscx::{component, html, props};
@@ -38,7 +39,7 @@ pub fn Flyout(props: FlyoutProps) -> String {
                             <div class="flex h-full flex-col overflow-y-scroll bg-white py-6 shadow-xl">
                                 <div class="px-4 sm:px-6">
                                     <div class="flex items-start justify-between">
-                                        <h2 class="text-base font-semibold leading-6 text-gray-900" id="slide-over-title">{props.title}</h2>
+                                        <h2 class="text-base font-semibold leading-6 text-gray-900" id="slide-over-title">{escape_text(&props.title)}</h2>
                                         <div class="ml-3 flex h-7 items-center">
                                             <button type="button" data-toggle-action="close" class="relative rounded-md bg-white text-gray-400 hover:text-gray-500 focus:outline-none focus:ring-2 focus:ring-indigo-500 focus:ring-offset-2">
                                                 <span class="absolute -inset-2.5"></span>
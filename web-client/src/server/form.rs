@@ -2,7 +2,7 @@ use rscx::{component, html, props};
 
 use web_macros::*;
 
-use super::{attrs::Attrs, html_element::HtmlElement};
+use super::{attrs::Attrs, class_list::ClassList, escape::escape_text, html_element::HtmlElement};
 use crate::server::yc_control::YcControl;
 
 #[html_element]
@@ -22,16 +22,16 @@ pub fn TextInput(props: TextInputProps) -> String {
     };
 
     let (tag, children) = match props.input_type.as_str() {
-        "textarea" => ("textarea", props.value.clone()),
+        "textarea" => ("textarea", escape_text(&props.value)),
         _ => ("input", "".to_string()),
     };
 
     html! {
         <HtmlElement
             tag=tag
-            id=props.name.clone()
             class=format!("block w-full rounded-md border-0 py-1.5 shadow-sm ring-1 ring-inset focus:ring-2 focus:ring-inset sm:text-sm sm:leading-6 {}", class)
-            attrs=spread_attrs!(props | omit(id, class)).set("type", props.input_type.clone())
+            attrs=spread_attrs!(props | omit(class) | default(id = props.name.clone()))
+                .set("type", props.input_type.clone())
             children=children
         />
         <ErrorMessage message=props.error />
@@ -59,7 +59,7 @@ pub fn Label(props: LabelProps) -> String {
     html! {
         <HtmlElement
             tag="label"
-            class=format!("block text-sm font-medium leading-6 {} {}", color, props.class).trim()
+            class=ClassList::from("block text-sm font-medium leading-6").merge(color).merge(&props.class)
             attrs=spread_attrs!(props | omit(class)).set("for", props.for_input)
         >
             {props.children}
@@ -84,9 +84,10 @@ pub fn Select(props: SelectProps) -> String {
     html! {
         <HtmlElement
             tag="select"
-            id=props.name.clone()
-            class=format!("block w-full rounded-md border-0 py-1.5 text-gray-900 shadow-sm ring-1 ring-inset ring-gray-300 focus:ring-2 focus:ring-inset focus:ring-indigo-600 sm:max-w-xs sm:text-sm sm:leading-6 {} {}", class, props.class).trim()
-            attrs=spread_attrs!(props | omit(id, class))
+            class=ClassList::from("block w-full rounded-md border-0 py-1.5 text-gray-900 shadow-sm ring-1 ring-inset ring-gray-300 focus:ring-2 focus:ring-inset focus:ring-indigo-600 sm:max-w-xs sm:text-sm sm:leading-6")
+                .merge(class)
+                .merge(&props.class)
+            attrs=spread_attrs!(props | omit(class) | default(id = props.name.clone()))
         >
             {props.children}
         </HtmlElement>
@@ -111,9 +112,8 @@ pub fn SelectOption(props: SelectOptionProps) -> String {
     html! {
         <HtmlElement
             tag="option"
-            id=props.name.clone()
-            attrs=spread_attrs!(props | omit(id))
-                .set_if("selected", "selected".into(), props.selected)
+            attrs=spread_attrs!(props | default(id = props.name.clone()))
+                .set_bool("selected", props.selected)
                 .set_if("label", props.label.clone(), !props.label.is_empty())
         >
             {props.children}
@@ -125,7 +125,7 @@ pub fn SelectOption(props: SelectOptionProps) -> String {
 fn ErrorMessage(message: Option<String>) -> String {
     if let Some(message) = message {
         html! {
-            <p class="text-sm text-red-600 dark:text-red-500">{message}</p>
+            <p class="text-sm text-red-600 dark:text-red-500">{escape_text(message)}</p>
         }
     } else {
         String::new()
@@ -150,7 +150,7 @@ pub fn Button(props: ButtonProps) -> String {
     html! {
         <HtmlElement
             tag="button"
-            class=format!("{} {}", css, props.class).trim()
+            class=ClassList::from(css).merge(&props.class)
             attrs=spread_attrs!(props | omit(class, name)).set("type", button_type)
         >
             {props.children}
@@ -199,7 +199,7 @@ pub fn FileInput(props: FileInputProps) -> String {
                                 String::from(
                                     Attrs::default()
                                         .set_if("accept", props.accept.clone(), !props.accept.is_empty())
-                                        .set_if("multiple", "true".into(), props.multiple)
+                                        .set_bool("multiple", props.multiple)
                                 )
                             }
                         />
@@ -227,7 +227,7 @@ pub fn GridLayout(props: GridLayoutProps) -> String {
     html! {
         <HtmlElement
             tag="div"
-            class=format!("grid grid-cols-1 gap-x-6 gap-y-8 sm:grid-cols-6 {}", props.class).trim()
+            class=ClassList::from("grid grid-cols-1 gap-x-6 gap-y-8 sm:grid-cols-6").merge(&props.class)
             attrs=spread_attrs!(props | omit(class))
         >
             {props.children}
@@ -264,13 +264,11 @@ pub fn GridCell(props: GridCellProps) -> String {
         <HtmlElement
             tag="div"
             class={
-                let mut classes = Vec::new();
-
                 // For now hardcode this layout of cells (col w/ .5rem gap)
                 // If we have other cell layouts, we can create new enum
-                classes.push("flex flex-col gap-2".to_string());
+                let mut classes = ClassList::from("flex flex-col gap-2");
 
-                classes.push(match props.span {
+                classes.add(match props.span {
                     // generates classes (for tailwind) in tailwind.config.js safelist
                     CellSpan::Size(size) => format!("sm:col-span-{}", size),
                     CellSpan::Full => "sm:col-span-full".to_string(),
@@ -278,14 +276,10 @@ pub fn GridCell(props: GridCellProps) -> String {
 
                 if props.start > 0 {
                     // generates classes (for tailwind) in tailwind.config.js safelist
-                    classes.push(format!("sm:col-start-{}", props.start));
-                }
-
-                if !props.class.is_empty() {
-                    classes.push(props.class);
+                    classes.add(format!("sm:col-start-{}", props.start));
                 }
 
-                classes.join(" ")
+                classes.merge(&props.class)
             }
             attrs=spread_attrs!(props | omit(class))
         >
@@ -293,3 +287,78 @@ pub fn GridCell(props: GridCellProps) -> String {
         </HtmlElement>
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_text_input_escapes_value() {
+        let html = html! {
+            <TextInput name="first_name" value="\"><script>alert('x')</script>" />
+        };
+
+        assert!(
+            html.contains("value=\"&quot;&gt;&lt;script&gt;alert(&#x27;x&#x27;)&lt;/script&gt;\"")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_text_input_is_void_element() {
+        let html = html! {
+            <TextInput name="first_name" attrs=Attrs::default().set_bool("required", true) />
+        };
+
+        assert!(html.starts_with("<input "));
+        assert!(html.contains(" required "));
+        assert!(html.ends_with(" />"));
+        assert!(!html.contains("</input>"));
+    }
+
+    #[tokio::test]
+    async fn test_text_input_id_defaults_to_name() {
+        let html = html! {
+            <TextInput name="first_name" />
+        };
+        assert!(html.contains("id=\"first_name\""));
+
+        let html = html! {
+            <TextInput name="first_name" id="worker-first-name" />
+        };
+        assert!(html.contains("id=\"worker-first-name\""));
+        assert!(html.contains("name=\"first_name\""));
+    }
+
+    #[tokio::test]
+    async fn test_select_option_selected() {
+        let html = html! {
+            <SelectOption selected=true>Blue</SelectOption>
+        };
+
+        assert_eq!(
+            html,
+            String::from("<option data-rsx=\"HtmlElement\" selected>Blue</option>")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_textarea_escapes_content() {
+        let html = html! {
+            <TextInput input_type="textarea" name="notes" value="</textarea><b>Tom & Jerry</b>" />
+        };
+
+        assert!(html.contains(">&lt;/textarea&gt;&lt;b&gt;Tom &amp; Jerry&lt;/b&gt;</textarea>"));
+    }
+
+    #[tokio::test]
+    async fn test_error_message_is_escaped() {
+        let html = html! {
+            <Select name="color" error=Some("<b>Blue</b> & Red".into())>
+                <SelectOption label="\"Red\"">Red</SelectOption>
+            </Select>
+        };
+
+        assert!(html.contains("label=\"&quot;Red&quot;\""));
+        assert!(html.contains(">&lt;b&gt;Blue&lt;/b&gt; &amp; Red</p>"));
+    }
+}
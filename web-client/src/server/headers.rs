@@ -1,3 +1,4 @@
+use super::escape::escape_text;
 use super::html_element::HtmlElement;
 use rscx::{component, html, props};
 use web_macros::*;
@@ -27,12 +28,12 @@ pub fn SecondaryHeader(props: SecondaryHeaderProps) -> String {
             <h2
                 class="text-lg font-medium leading-6 text-gray-900"
             >
-                {props.title}
+                {escape_text(&props.title)}
             </h2>
             {
                 if !props.subtitle.is_empty() {
                     html! {
-                        <p class="mt-1 text-sm text-gray-500">{props.subtitle}</p>
+                        <p class="mt-1 text-sm text-gray-500">{escape_text(&props.subtitle)}</p>
                     }
                 } else {
                     "".into()
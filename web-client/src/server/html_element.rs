@@ -1,13 +1,27 @@
-use super::opt_attrs::opt_attrs;
+use super::attrs::Attrs;
+use super::escape::escape_text;
 use rscx::{component, props};
-use std::collections::HashMap;
 use web_macros::*;
 
+// https://html.spec.whatwg.org/multipage/syntax.html#void-elements
+pub const VOID_ELEMENTS: [&str; 13] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+pub fn is_void_element<S: AsRef<str>>(tag: S) -> bool {
+    VOID_ELEMENTS.contains(&tag.as_ref().to_ascii_lowercase().as_str())
+}
+
 #[html_element]
 pub struct HtmlElementProps {
     #[builder(default)]
     children: String,
 
+    // Plain text content. Unlike `children` (which is trusted markup), this is html escaped.
+    #[builder(setter(into), default)]
+    text: String,
+
     #[builder(setter(into), default=String::from("HtmlElement"))]
     component_name: String,
 
@@ -17,23 +31,28 @@ pub struct HtmlElementProps {
 
 #[component]
 pub fn HtmlElement(props: HtmlElementProps) -> String {
-    let attrs = opt_attrs(
-        HashMap::from([("data-rsx", props.component_name.clone())])
-            .into_iter()
-            .chain(props.html_attrs_to_hashmap())
-            .collect::<HashMap<&str, String>>(),
+    let attrs = String::from(
+        Attrs::from(props.html_attrs_to_hashmap()).set("data-rsx", props.component_name.clone()),
     );
 
+    // Void elements can't have content or an end tag.
+    if is_void_element(&props.tag) {
+        return format!("<{} {} />", props.tag, attrs);
+    }
+
     format!(
-        "<{} {}>{}</{}>",
-        props.tag, attrs, props.children, props.tag
+        "<{} {}>{}{}</{}>",
+        props.tag,
+        attrs,
+        escape_text(&props.text),
+        props.children,
+        props.tag
     )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::server::attrs::Attrs;
     use rscx::html;
 
     #[tokio::test]
@@ -57,6 +76,47 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_with_void_tag() {
+        let html = html! {
+            <HtmlElement tag="input" name="email" />
+        };
+
+        assert_eq!(
+            html,
+            String::from("<input data-rsx=\"HtmlElement\" name=\"email\" />")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_with_void_tag_ignores_children() {
+        let html = html! {
+            <HtmlElement tag="br">
+                <p>Should not render.</p>
+            </HtmlElement>
+        };
+
+        assert_eq!(html, String::from("<br data-rsx=\"HtmlElement\" />"));
+    }
+
+    #[tokio::test]
+    async fn test_with_boolean_attrs() {
+        let html = html! {
+            <HtmlElement
+                tag="input"
+                attrs=Attrs::default()
+                    .set_bool("disabled", true)
+                    .set_bool("required", true)
+                    .set_bool("checked", false)
+            />
+        };
+
+        assert_eq!(
+            html,
+            String::from("<input data-rsx=\"HtmlElement\" disabled required />")
+        );
+    }
+
     #[tokio::test]
     async fn test_with_children() {
         let html = html! {
@@ -90,6 +150,57 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_with_text_is_escaped() {
+        let html = html! {
+            <HtmlElement tag="p" text="<b>Tom & Jerry</b>">
+                <i>trusted</i>
+            </HtmlElement>
+        };
+
+        assert_eq!(
+            html,
+            String::from(
+                "<p data-rsx=\"HtmlElement\">&lt;b&gt;Tom &amp; Jerry&lt;/b&gt;<i>trusted</i></p>"
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_with_attribute_values_escaped() {
+        let html = html! {
+            <HtmlElement
+                id="\" onmouseover=\"alert('x')"
+                class="a < b && c > d"
+                attrs=Attrs::with("data-foo", "\"><script>alert(1)</script>".into())
+            />
+        };
+
+        assert_eq!(
+            html,
+            String::from(
+                "<div class=\"a &lt; b &amp;&amp; c &gt; d\" data-foo=\"&quot;&gt;&lt;script&gt;alert(1)&lt;/script&gt;\" data-rsx=\"HtmlElement\" id=\"&quot; onmouseover=&quot;alert(&#x27;x&#x27;)\"></div>"
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_with_class_overridden_by_attrs() {
+        let html = html! {
+            <HtmlElement
+                class="rounded px-2 py-1 text-sm"
+                attrs=Attrs::with("class", "px-4 rounded".into())
+            />
+        };
+
+        assert_eq!(
+            html,
+            String::from(
+                "<div class=\"py-1 text-sm px-4 rounded\" data-rsx=\"HtmlElement\"></div>"
+            )
+        );
+    }
+
     #[tokio::test]
     async fn test_with_attrs_with_omit() {
         // Emulate usage by a component
@@ -118,4 +229,123 @@ mod tests {
             )
         );
     }
+
+    #[html_element(global, aria, link, "data-testid", "hx-on:click")]
+    struct TestLinkProps {
+        #[builder(default)]
+        children: String,
+
+        // Declared fields win over the generated attribute fields.
+        #[builder(setter(into), default=String::from("_self"))]
+        target: String,
+    }
+
+    #[component]
+    fn TestLink(props: TestLinkProps) -> String {
+        html! {
+            <HtmlElement
+                tag="a"
+                attrs=spread_attrs!(props | omit(title)).set("target", props.target)
+            >
+                {props.children}
+            </HtmlElement>
+        }
+    }
+
+    #[tokio::test]
+    async fn test_with_attribute_groups() {
+        let html = html! {
+            <TestLink
+                href="/workers"
+                aria_label="Workers"
+                data_testid="workers-link"
+                hx_on_click="htmx.trigger(this, 'opened')"
+                title="omitted"
+            >
+                Workers
+            </TestLink>
+        };
+
+        assert_eq!(
+            html,
+            String::from(
+                "<a aria-label=\"Workers\" data-rsx=\"HtmlElement\" data-testid=\"workers-link\" href=\"/workers\" hx-on:click=\"htmx.trigger(this, &#x27;opened&#x27;)\" target=\"_self\">Workers</a>"
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_with_attribute_groups_merges_attrs() {
+        let html = html! {
+            <TestLink
+                class="underline"
+                rel="noopener"
+                attrs=Attrs::with("class", "font-bold".into()).set("title", "From attrs".into())
+            >
+                Workers
+            </TestLink>
+        };
+
+        assert_eq!(
+            html,
+            String::from(
+                "<a class=\"underline font-bold\" data-rsx=\"HtmlElement\" rel=\"noopener\" target=\"_self\" title=\"From attrs\">Workers</a>"
+            )
+        );
+    }
+
+    #[html_element(global, aria, link)]
+    struct TestPickedLinkProps {
+        #[builder(default)]
+        children: String,
+    }
+
+    #[component]
+    fn TestPickedLink(props: TestPickedLinkProps) -> String {
+        html! {
+            <HtmlElement
+                tag="a"
+                attrs=spread_attrs!(props | pick(href, title, aria_label) | rename(title => aria_label) | default(href = "#"))
+            >
+                {props.children}
+            </HtmlElement>
+        }
+    }
+
+    #[tokio::test]
+    async fn test_with_transformer_pipeline() {
+        let html = html! {
+            <TestPickedLink class="underline" title="Workers" rel="noopener">Workers</TestPickedLink>
+        };
+
+        assert_eq!(
+            html,
+            String::from(
+                "<a aria-label=\"Workers\" data-rsx=\"HtmlElement\" href=\"#\">Workers</a>"
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_with_hx_builder() {
+        use crate::server::hx::{Hx, SwapStrategy, Target};
+
+        let html = html! {
+            <HtmlElement
+                tag="button"
+                hx_target="#replaced-by-hx"
+                hx_confirm="Are you sure?"
+                hx=Hx::delete("/workers/1").target(Target::Closest("tr".into())).swap(SwapStrategy::OuterHtml)
+            >
+                Delete
+            </HtmlElement>
+        };
+
+        assert_eq!(
+            html,
+            String::from(
+                "<button data-rsx=\"HtmlElement\" hx-confirm=\"Are you sure?\" hx-delete=\"/workers/1\" hx-swap=\"outerHTML\" hx-target=\"closest tr\">Delete</button>"
+            )
+        );
+    }
 }
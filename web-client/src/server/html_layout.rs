@@ -1,15 +1,7 @@
-use once_cell::sync::Lazy;
+use super::csp::{nonce, Script};
+use super::escape::escape_attribute;
+use crate::assets::asset_url;
 use rscx::{component, html, props};
-use std::time::{SystemTime, UNIX_EPOCH};
-
-// TEMP HACK! Used to bust cache on client scripts and stylesheets.
-// TODO Get hash of each build file and use that.
-static TS: Lazy<u128> = Lazy::new(|| {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_millis()
-});
 
 #[props]
 pub struct HtmlLayoutProps {
@@ -35,8 +27,9 @@ pub fn HtmlLayout(props: HtmlLayoutProps) -> String {
                 <meta charset="utf-8" />
                 <meta name="viewport" content="width=device-width, initial-scale=1" />
                 <title>{props.head_title}</title>
-                <link href={format!("/client/common.css?ts={}", *TS)} rel="stylesheet" />
-                <script>{
+                <link href=asset_url("common.css") rel="stylesheet" />
+                <HtmxConfig />
+                <Script>{
                     "window.YcControls = {
                         attachOnReadyQueue: [],
                         attach: function(element) {
@@ -47,14 +40,27 @@ pub fn HtmlLayout(props: HtmlLayoutProps) -> String {
                             this.onReadyQueue.push(onReadyHandler);
                         },
                     };"
-                }</script>
+                }</Script>
                 {props.head_links}
                 {props.head_scripts}
             </head>
             <body>
                 {props.children}
-                <script src={format!("/client/common.js?ts={}", *TS)}></script>
+                <Script src=asset_url("common.js") />
             </body>
         </html>
     }
 }
+
+// htmx evaluates `<script>`s in swapped content by recreating them, they get the page's nonce.
+// https://htmx.org/docs/#security
+#[component]
+fn HtmxConfig() -> String {
+    match nonce() {
+        Some(nonce) => format!(
+            r#"<meta name="htmx-config" content="{}" />"#,
+            escape_attribute(serde_json::json!({ "inlineScriptNonce": nonce }).to_string())
+        ),
+        None => String::new(),
+    }
+}
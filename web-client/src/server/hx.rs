@@ -0,0 +1,601 @@
+use std::fmt;
+use std::time::Duration;
+
+use serde::{ser::SerializeMap, Serialize, Serializer};
+
+use super::attrs::Attrs;
+
+/**
+ * Hx
+ *
+ * Typed builder for htmx attributes. Every `#[html_element]` component has an `hx` prop:
+ *
+ * <PrimaryButton hx=Hx::get("/workers").target("#modals-root").swap(SwapStrategy::BeforeEnd)>
+ *
+ * It also converts into `Attrs` for components and elements that take `attrs`.
+ * https://htmx.org/reference/#attributes
+ */
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Hx {
+    attrs: Vec<(String, String)>,
+}
+
+impl Hx {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn get<S: Into<String>>(url: S) -> Self {
+        Self::new().set("hx-get", url)
+    }
+    pub fn post<S: Into<String>>(url: S) -> Self {
+        Self::new().set("hx-post", url)
+    }
+    pub fn put<S: Into<String>>(url: S) -> Self {
+        Self::new().set("hx-put", url)
+    }
+    pub fn patch<S: Into<String>>(url: S) -> Self {
+        Self::new().set("hx-patch", url)
+    }
+    pub fn delete<S: Into<String>>(url: S) -> Self {
+        Self::new().set("hx-delete", url)
+    }
+    pub fn target<T: Into<Target>>(self, target: T) -> Self {
+        self.set("hx-target", target.into().to_string())
+    }
+    pub fn swap<S: Into<Swap>>(self, swap: S) -> Self {
+        self.set("hx-swap", swap.into().to_string())
+    }
+    pub fn swap_oob<S: Into<Swap>>(self, swap: S) -> Self {
+        self.set("hx-swap-oob", swap.into().to_string())
+    }
+    // Can be called more than once, triggers are combined.
+    pub fn trigger<T: Into<Trigger>>(self, trigger: T) -> Self {
+        let trigger = trigger.into().to_string();
+        let triggers = match self.attr("hx-trigger") {
+            Some(triggers) => format!("{}, {}", triggers, trigger),
+            None => trigger,
+        };
+        self.set("hx-trigger", triggers)
+    }
+    pub fn sync<T: Into<Target>>(self, element: T, strategy: SyncStrategy) -> Self {
+        self.set("hx-sync", format!("{}:{}", element.into(), strategy))
+    }
+    pub fn vals<T: ?Sized + Serialize>(self, vals: &T) -> Self {
+        self.set("hx-vals", to_json(vals))
+    }
+    pub fn headers<T: ?Sized + Serialize>(self, headers: &T) -> Self {
+        self.set("hx-headers", to_json(headers))
+    }
+    pub fn select<S: Into<String>>(self, selector: S) -> Self {
+        self.set("hx-select", selector)
+    }
+    pub fn select_oob<S: Into<String>>(self, selectors: S) -> Self {
+        self.set("hx-select-oob", selectors)
+    }
+    pub fn include<T: Into<Target>>(self, target: T) -> Self {
+        self.set("hx-include", target.into().to_string())
+    }
+    pub fn indicator<S: Into<String>>(self, selector: S) -> Self {
+        self.set("hx-indicator", selector)
+    }
+    pub fn disabled_elt<T: Into<Target>>(self, target: T) -> Self {
+        self.set("hx-disabled-elt", target.into().to_string())
+    }
+    pub fn confirm<S: Into<String>>(self, message: S) -> Self {
+        self.set("hx-confirm", message)
+    }
+    pub fn prompt<S: Into<String>>(self, message: S) -> Self {
+        self.set("hx-prompt", message)
+    }
+    pub fn push_url<U: Into<HistoryUrl>>(self, url: U) -> Self {
+        self.set("hx-push-url", url.into().to_string())
+    }
+    pub fn replace_url<U: Into<HistoryUrl>>(self, url: U) -> Self {
+        self.set("hx-replace-url", url.into().to_string())
+    }
+    pub fn boost(self, boost: bool) -> Self {
+        self.set("hx-boost", boost.to_string())
+    }
+    pub fn encoding_multipart(self) -> Self {
+        self.set("hx-encoding", "multipart/form-data")
+    }
+    pub fn ext<S: Into<String>>(self, extensions: S) -> Self {
+        self.set("hx-ext", extensions)
+    }
+    // `hx-on:<event>`. htmx events take one leading `:`: `.on(":after-request", "...")`.
+    pub fn on<E: AsRef<str>, S: Into<String>>(self, event: E, script: S) -> Self {
+        self.set(format!("hx-on:{}", event.as_ref()), script)
+    }
+    pub fn attr(&self, key: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+    pub fn is_empty(&self) -> bool {
+        self.attrs.is_empty()
+    }
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.attrs.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    fn set<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        let key = key.into();
+        let value = value.into();
+
+        match self.attrs.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, v)) => *v = value,
+            None => self.attrs.push((key, value)),
+        }
+
+        self
+    }
+}
+
+impl From<Hx> for Attrs {
+    fn from(hx: Hx) -> Self {
+        hx.attrs
+            .into_iter()
+            .fold(Attrs::default(), |attrs, (key, value)| {
+                attrs.set(key, value)
+            })
+    }
+}
+
+// Serializes as an object of attributes: `{"hx-post":"/workers/1/restore","hx-swap":"none"}`
+impl Serialize for Hx {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.attrs.len()))?;
+        for (key, value) in self.iter() {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+fn to_json<T: ?Sized + Serialize>(value: &T) -> String {
+    serde_json::to_string(value).expect("htmx attribute values should serialize to JSON")
+}
+
+// htmx time format: `500ms`, `2s`
+fn format_duration(duration: &Duration) -> String {
+    if duration.as_secs() > 0 && duration.subsec_millis() == 0 {
+        format!("{}s", duration.as_secs())
+    } else {
+        format!("{}ms", duration.as_millis())
+    }
+}
+
+// https://htmx.org/attributes/hx-swap/ ///////////////////////
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SwapStrategy {
+    InnerHtml,
+    OuterHtml,
+    BeforeBegin,
+    AfterBegin,
+    BeforeEnd,
+    AfterEnd,
+    Delete,
+    None,
+}
+
+impl fmt::Display for SwapStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SwapStrategy::InnerHtml => "innerHTML",
+            SwapStrategy::OuterHtml => "outerHTML",
+            SwapStrategy::BeforeBegin => "beforebegin",
+            SwapStrategy::AfterBegin => "afterbegin",
+            SwapStrategy::BeforeEnd => "beforeend",
+            SwapStrategy::AfterEnd => "afterend",
+            SwapStrategy::Delete => "delete",
+            SwapStrategy::None => "none",
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ScrollPosition {
+    Top,
+    Bottom,
+}
+
+impl fmt::Display for ScrollPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ScrollPosition::Top => "top",
+            ScrollPosition::Bottom => "bottom",
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SwapModifier {
+    Transition(bool),
+    Swap(Duration),
+    Settle(Duration),
+    IgnoreTitle(bool),
+    FocusScroll(bool),
+    // An optional selector to scroll, the swapped element otherwise.
+    Scroll(Option<String>, ScrollPosition),
+    Show(Option<String>, ScrollPosition),
+    ShowNone,
+}
+
+impl fmt::Display for SwapModifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SwapModifier::Transition(on) => write!(f, "transition:{}", on),
+            SwapModifier::Swap(delay) => write!(f, "swap:{}", format_duration(delay)),
+            SwapModifier::Settle(delay) => write!(f, "settle:{}", format_duration(delay)),
+            SwapModifier::IgnoreTitle(on) => write!(f, "ignoreTitle:{}", on),
+            SwapModifier::FocusScroll(on) => write!(f, "focus-scroll:{}", on),
+            SwapModifier::Scroll(Some(selector), position) => {
+                write!(f, "scroll:{}:{}", selector, position)
+            }
+            SwapModifier::Scroll(None, position) => write!(f, "scroll:{}", position),
+            SwapModifier::Show(Some(selector), position) => {
+                write!(f, "show:{}:{}", selector, position)
+            }
+            SwapModifier::Show(None, position) => write!(f, "show:{}", position),
+            SwapModifier::ShowNone => f.write_str("show:none"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Swap {
+    strategy: SwapStrategy,
+    modifiers: Vec<SwapModifier>,
+}
+
+impl Swap {
+    pub fn new(strategy: SwapStrategy) -> Self {
+        Self {
+            strategy,
+            modifiers: vec![],
+        }
+    }
+    pub fn modifier(mut self, modifier: SwapModifier) -> Self {
+        self.modifiers.push(modifier);
+        self
+    }
+}
+
+impl From<SwapStrategy> for Swap {
+    fn from(strategy: SwapStrategy) -> Self {
+        Swap::new(strategy)
+    }
+}
+
+impl fmt::Display for Swap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.strategy)?;
+        for modifier in &self.modifiers {
+            write!(f, " {}", modifier)?;
+        }
+        Ok(())
+    }
+}
+
+// https://htmx.org/attributes/hx-target/ /////////////////////
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Target {
+    This,
+    Selector(String),
+    Closest(String),
+    Find(String),
+    Next(Option<String>),
+    Previous(Option<String>),
+}
+
+impl From<&str> for Target {
+    fn from(selector: &str) -> Self {
+        Target::Selector(selector.to_string())
+    }
+}
+
+impl From<String> for Target {
+    fn from(selector: String) -> Self {
+        Target::Selector(selector)
+    }
+}
+
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Target::This => f.write_str("this"),
+            Target::Selector(selector) => f.write_str(selector),
+            Target::Closest(selector) => write!(f, "closest {}", selector),
+            Target::Find(selector) => write!(f, "find {}", selector),
+            Target::Next(Some(selector)) => write!(f, "next {}", selector),
+            Target::Next(None) => f.write_str("next"),
+            Target::Previous(Some(selector)) => write!(f, "previous {}", selector),
+            Target::Previous(None) => f.write_str("previous"),
+        }
+    }
+}
+
+// https://htmx.org/attributes/hx-trigger/ ////////////////////
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum QueueMode {
+    First,
+    Last,
+    All,
+    None,
+}
+
+impl fmt::Display for QueueMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            QueueMode::First => "first",
+            QueueMode::Last => "last",
+            QueueMode::All => "all",
+            QueueMode::None => "none",
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TriggerModifier {
+    Once,
+    Changed,
+    Delay(Duration),
+    Throttle(Duration),
+    From(String),
+    Target(String),
+    Consume,
+    Queue(QueueMode),
+}
+
+impl fmt::Display for TriggerModifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TriggerModifier::Once => f.write_str("once"),
+            TriggerModifier::Changed => f.write_str("changed"),
+            TriggerModifier::Delay(delay) => write!(f, "delay:{}", format_duration(delay)),
+            TriggerModifier::Throttle(delay) => write!(f, "throttle:{}", format_duration(delay)),
+            TriggerModifier::From(selector) => write!(f, "from:{}", selector),
+            TriggerModifier::Target(selector) => write!(f, "target:{}", selector),
+            TriggerModifier::Consume => f.write_str("consume"),
+            TriggerModifier::Queue(mode) => write!(f, "queue:{}", mode),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trigger {
+    event: String,
+    filter: Option<String>,
+    modifiers: Vec<TriggerModifier>,
+}
+
+impl Trigger {
+    pub fn event<S: Into<String>>(event: S) -> Self {
+        Self {
+            event: event.into(),
+            filter: None,
+            modifiers: vec![],
+        }
+    }
+    pub fn load() -> Self {
+        Self::event("load")
+    }
+    pub fn revealed() -> Self {
+        Self::event("revealed")
+    }
+    pub fn intersect() -> Self {
+        Self::event("intersect")
+    }
+    // Polling: `every 2s`
+    pub fn every(interval: Duration) -> Self {
+        Self::event(format!("every {}", format_duration(&interval)))
+    }
+    // A javascript expression, ex: `ctrlKey`. Rendered as `click[ctrlKey]`.
+    pub fn filter<S: Into<String>>(mut self, filter: S) -> Self {
+        self.filter = Some(filter.into());
+        self
+    }
+    pub fn once(self) -> Self {
+        self.modifier(TriggerModifier::Once)
+    }
+    pub fn changed(self) -> Self {
+        self.modifier(TriggerModifier::Changed)
+    }
+    pub fn delay(self, delay: Duration) -> Self {
+        self.modifier(TriggerModifier::Delay(delay))
+    }
+    pub fn throttle(self, delay: Duration) -> Self {
+        self.modifier(TriggerModifier::Throttle(delay))
+    }
+    pub fn from_element<S: Into<String>>(self, selector: S) -> Self {
+        self.modifier(TriggerModifier::From(selector.into()))
+    }
+    pub fn modifier(mut self, modifier: TriggerModifier) -> Self {
+        self.modifiers.push(modifier);
+        self
+    }
+}
+
+impl From<&str> for Trigger {
+    fn from(event: &str) -> Self {
+        Trigger::event(event)
+    }
+}
+
+impl fmt::Display for Trigger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.event)?;
+        if let Some(filter) = &self.filter {
+            write!(f, "[{}]", filter)?;
+        }
+        for modifier in &self.modifiers {
+            write!(f, " {}", modifier)?;
+        }
+        Ok(())
+    }
+}
+
+// https://htmx.org/attributes/hx-sync/ ///////////////////////
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SyncStrategy {
+    Drop,
+    Abort,
+    Replace,
+    Queue(QueueMode),
+}
+
+impl fmt::Display for SyncStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SyncStrategy::Drop => f.write_str("drop"),
+            SyncStrategy::Abort => f.write_str("abort"),
+            SyncStrategy::Replace => f.write_str("replace"),
+            SyncStrategy::Queue(mode) => write!(f, "queue {}", mode),
+        }
+    }
+}
+
+// https://htmx.org/attributes/hx-push-url/ ///////////////////
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum HistoryUrl {
+    Enabled(bool),
+    Url(String),
+}
+
+impl From<bool> for HistoryUrl {
+    fn from(enabled: bool) -> Self {
+        HistoryUrl::Enabled(enabled)
+    }
+}
+
+impl From<&str> for HistoryUrl {
+    fn from(url: &str) -> Self {
+        HistoryUrl::Url(url.to_string())
+    }
+}
+
+impl From<String> for HistoryUrl {
+    fn from(url: String) -> Self {
+        HistoryUrl::Url(url)
+    }
+}
+
+impl fmt::Display for HistoryUrl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HistoryUrl::Enabled(enabled) => write!(f, "{}", enabled),
+            HistoryUrl::Url(url) => f.write_str(url),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_request_with_target_and_swap() {
+        let hx = Hx::get("/playground/modals/modal-one")
+            .target("#modals-root")
+            .swap(SwapStrategy::OuterHtml);
+
+        assert_eq!(
+            String::from(Attrs::from(hx)),
+            String::from(
+                "hx-get=\"/playground/modals/modal-one\" hx-swap=\"outerHTML\" hx-target=\"#modals-root\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_swap_modifiers() {
+        let swap = Swap::new(SwapStrategy::BeforeEnd)
+            .modifier(SwapModifier::Settle(Duration::from_millis(250)))
+            .modifier(SwapModifier::Swap(Duration::from_secs(1)))
+            .modifier(SwapModifier::Scroll(None, ScrollPosition::Bottom))
+            .modifier(SwapModifier::Show(
+                Some("#list".into()),
+                ScrollPosition::Top,
+            ));
+
+        assert_eq!(
+            swap.to_string(),
+            "beforeend settle:250ms swap:1s scroll:bottom show:#list:top"
+        );
+    }
+
+    #[test]
+    fn test_triggers() {
+        let hx = Hx::post("/search")
+            .trigger(
+                Trigger::event("keyup")
+                    .changed()
+                    .delay(Duration::from_millis(500)),
+            )
+            .trigger(Trigger::event("click").filter("ctrlKey").once())
+            .trigger(Trigger::every(Duration::from_secs(2)))
+            .trigger(Trigger::revealed().throttle(Duration::from_secs(1)));
+
+        assert_eq!(
+            hx.attr("hx-trigger"),
+            Some("keyup changed delay:500ms, click[ctrlKey] once, every 2s, revealed throttle:1s")
+        );
+    }
+
+    #[test]
+    fn test_targets_and_sync() {
+        let hx = Hx::delete("/workers/1")
+            .target(Target::Closest("tr".into()))
+            .sync(Target::Closest("form".into()), SyncStrategy::Abort)
+            .include(Target::Find("input".into()));
+
+        assert_eq!(hx.attr("hx-target"), Some("closest tr"));
+        assert_eq!(hx.attr("hx-sync"), Some("closest form:abort"));
+        assert_eq!(hx.attr("hx-include"), Some("find input"));
+
+        let hx = hx.sync(Target::This, SyncStrategy::Queue(QueueMode::Last));
+        assert_eq!(hx.attr("hx-sync"), Some("this:queue last"));
+    }
+
+    #[test]
+    fn test_vals_are_json_and_escaped() {
+        let hx = Hx::post("/notes").vals(&json!({ "note": "\"Tom\" & <Jerry>" }));
+
+        assert_eq!(hx.attr("hx-vals"), Some(r#"{"note":"\"Tom\" & <Jerry>"}"#));
+        assert_eq!(
+            String::from(Attrs::from(hx)),
+            String::from(
+                "hx-post=\"/notes\" hx-vals=\"{&quot;note&quot;:&quot;\\&quot;Tom\\&quot; &amp; &lt;Jerry&gt;&quot;}\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_setting_an_attribute_twice_replaces_it() {
+        let hx = Hx::get("/a")
+            .push_url(true)
+            .push_url("/b")
+            .on(":after-request", "this.reset()");
+
+        assert_eq!(hx.attr("hx-push-url"), Some("/b"));
+        assert_eq!(hx.attr("hx-on::after-request"), Some("this.reset()"));
+        assert_eq!(hx.iter().count(), 3);
+    }
+
+    #[test]
+    fn test_serializes_as_attribute_object() {
+        let hx = Hx::post("/workers/1/restore").swap(SwapStrategy::None);
+
+        assert_eq!(
+            serde_json::to_value(&hx).unwrap(),
+            json!({ "hx-post": "/workers/1/restore", "hx-swap": "none" })
+        );
+    }
+}
@@ -1,6 +1,13 @@
+use super::attrs::Attrs;
+use super::class_list::ClassList;
+use super::escape::{escape_js, escape_text};
+use super::html_element::HtmlElement;
+use super::hx::{Hx, SwapStrategy};
 use super::transition::Transition;
 use super::yc_control::YcControlJsApi;
 use rscx::{component, html, props, CollectFragmentAsync};
+use serde::Serialize;
+use std::time::Duration;
 
 /**
  * NotificationLiveRegion
@@ -18,10 +25,15 @@ pub fn NotificationLiveRegion() -> String {
             <template id="tpl-notification">
                 <SimpleNotification icon_svg=IconSvg::Info />
             </template>
+            <template id="tpl-notification-action">
+                <NotificationActionButton action=NotificationAction::new("", Hx::new()) />
+            </template>
             <template id="tpl-notification-icons">
                 <NotificationIcon svg=IconSvg::Success/>
                 <NotificationIcon svg=IconSvg::Error/>
                 <NotificationIcon svg=IconSvg::Info/>
+                <NotificationIcon svg=IconSvg::Warning/>
+                <NotificationIcon svg=IconSvg::Debug/>
                 // Add any additional prerendered icons here.
             </template>
         </div>
@@ -38,6 +50,9 @@ pub struct SimpleNotificationProps {
 
     #[builder(setter(into))]
     icon_svg: IconSvg,
+
+    #[builder(default)]
+    actions: Vec<NotificationAction>,
 }
 
 #[component]
@@ -52,8 +67,20 @@ pub fn SimpleNotification(props: SimpleNotificationProps) -> String {
                         <NotificationIcon svg=props.icon_svg />
                     </div>
                     <div class="ml-3 w-0 flex-1 pt-0.5">
-                        <p class="text-sm font-medium text-gray-900" data-notification-title>{props.title}</p>
-                        <p class="mt-1 text-sm text-gray-500" data-notification-message>{props.message}</p>
+                        <p class="text-sm font-medium text-gray-900" data-notification-title>{escape_text(&props.title)}</p>
+                        <p class="mt-1 text-sm text-gray-500" data-notification-message>{escape_text(&props.message)}</p>
+                        <div class="mt-3 flex space-x-7 empty:hidden" data-notification-actions>
+                            {
+                                props
+                                    .actions
+                                    .into_iter()
+                                    .map(|action| async move {
+                                        html! { <NotificationActionButton action=action /> }
+                                    })
+                                    .collect_fragment_async()
+                                    .await
+                            }
+                        </div>
                     </div>
                     <NoticationCloseButton />
                 </div>
@@ -66,6 +93,8 @@ pub enum IconSvg {
     Success,
     Error,
     Info,
+    Warning,
+    Debug,
     Custom(String),
 }
 
@@ -98,6 +127,16 @@ fn NotificationIcon(props: NotificationIconProps) -> String {
                 <path stroke-linecap="round" stroke-linejoin="round" d="M11.25 11.25l.041-.02a.75.75 0 011.063.852l-.708 2.836a.75.75 0 001.063.853l.041-.021M21 12a9 9 0 11-18 0 9 9 0 0118 0zm-9-3.75h.008v.008H12V8.25z" />
             </svg>
         },
+        IconSvg::Warning => html! {
+            <svg class="h-6 w-6 text-yellow-400" data-notification-icon="warning" fill="none" viewBox="0 0 24 24" stroke-width="1.5" stroke="currentColor" aria-hidden="true">
+                <path stroke-linecap="round" stroke-linejoin="round" d="M12 9v3.75m-9.303 3.376c-.866 1.5.217 3.374 1.948 3.374h14.71c1.73 0 2.813-1.874 1.948-3.374L13.949 3.378c-.866-1.5-3.032-1.5-3.898 0L2.697 16.126zM12 15.75h.007v.008H12v-.008z" />
+            </svg>
+        },
+        IconSvg::Debug => html! {
+            <svg class="h-6 w-6 text-gray-400" data-notification-icon="debug" fill="none" viewBox="0 0 24 24" stroke-width="1.5" stroke="currentColor" aria-hidden="true">
+                <path stroke-linecap="round" stroke-linejoin="round" d="M6.75 7.5l3 2.25-3 2.25m4.5 0h3m-9 8.25h13.5A2.25 2.25 0 0021 18V6a2.25 2.25 0 00-2.25-2.25H5.25A2.25 2.25 0 003 6v12a2.25 2.25 0 002.25 2.25z" />
+            </svg>
+        },
         IconSvg::Custom(svg) => svg,
     }
 }
@@ -121,25 +160,29 @@ pub fn NotificationFlashes(props: NotificationFlashesProps) -> String {
         .flashes
         .into_iter()
         .map(|(level, message)| async move {
-            let js_notification_fn = match level {
-                axum_flash::Level::Success => "showSuccessNotification",
-                axum_flash::Level::Error => "showErrorNotification",
-                _ => "showErrorNotification", // TODO! Replace with generic notification.
-            };
-
-            let message = serde_json::to_string(&message).unwrap();
-
             html! {
-                <YcControlJsApi call=format!("{}({})", js_notification_fn, message) />
+                <YcControlJsApi call=format!("{}({})", flash_notification_fn(level), escape_js(&message)) />
             }
         })
         .collect_fragment_async()
         .await
 }
 
+// Notification.ts function showing a flash of this level.
+fn flash_notification_fn(level: axum_flash::Level) -> &'static str {
+    match level {
+        axum_flash::Level::Success => "showSuccessNotification",
+        axum_flash::Level::Error => "showErrorNotification",
+        axum_flash::Level::Warning => "showWarningNotification",
+        axum_flash::Level::Info => "showInfoNotification",
+        axum_flash::Level::Debug => "showDebugNotification",
+    }
+}
+
 pub enum NotificationCall {
     Success(String),
     Error(String),
+    Warning(String),
     Info(String, String), // title, message
     Template,
     TemplateSelector(String),
@@ -156,48 +199,258 @@ pub struct NotificationPresenterProps {
     call: NotificationCall,
 
     #[builder(default)]
-    children: String,
-}
+    options: NotificationOptions,
 
-fn js_enc<T>(data: &T) -> String
-where
-    T: ?Sized + serde::Serialize,
-{
-    serde_json::to_string::<T>(data).unwrap()
+    #[builder(default)]
+    children: String,
 }
 
 #[component]
 pub fn NotificationPresenter(props: NotificationPresenterProps) -> String {
-    let api_call = match props.call {
+    // Arguments are already JS expressions.
+    let (function, mut args) = match props.call {
         NotificationCall::Success(message) => {
-            format!("showSuccessNotification({})", js_enc(&message))
-        }
-        NotificationCall::Error(message) => {
-            format!("showErrorNotification({})", js_enc(&message))
+            ("showSuccessNotification", vec![escape_js(&message)])
         }
-        NotificationCall::Info(title, message) => {
-            format!("showNotification({}, {})", js_enc(&title), js_enc(&message))
-        }
-        NotificationCall::TemplateSelector(templateSelector) => {
-            format!(
-                "showNotificationWithTemplate({})",
-                js_enc(&templateSelector),
-            )
+        NotificationCall::Error(message) => ("showErrorNotification", vec![escape_js(&message)]),
+        NotificationCall::Warning(message) => {
+            ("showWarningNotification", vec![escape_js(&message)])
         }
+        NotificationCall::Info(title, message) => (
+            "showNotification",
+            vec![escape_js(&title), escape_js(&message)],
+        ),
+        NotificationCall::TemplateSelector(templateSelector) => (
+            "showNotificationWithTemplate",
+            vec![escape_js(&templateSelector)],
+        ),
         NotificationCall::Template => {
             if props.children.is_empty() {
                 panic!("NotificationPresenter: Template call requires children.")
             }
-            "showNotificationWithTemplate(callerScript.nextElementSibling)".into()
+            (
+                "showNotificationWithTemplate",
+                vec!["callerScript.nextElementSibling".to_string()],
+            )
         }
     };
 
+    if !props.options.is_empty() {
+        args.push(escape_js(&props.options));
+    }
+
+    let api_call = format!("{}({})", function, args.join(", "));
+
     html! {
         <YcControlJsApi call=api_call />
         {props.children}
     }
 }
 
+// #### Notification options. ###############
+
+/**
+ * NotificationOptions
+ *
+ * Action buttons and how long a notification stays open. Notifications close after 5 seconds,
+ * errors stay open unless they're given a timeout.
+ *
+ * <NotificationPresenter
+ *     call=NotificationCall::Success("Worker deleted.".into())
+ *     options=NotificationOptions::new()
+ *         .action(NotificationAction::new("Undo", Hx::post(format!("/workers/{}/restore", id))))
+ *         .timeout(Duration::from_secs(10))
+ * />
+ */
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct NotificationOptions {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    actions: Vec<NotificationAction>,
+
+    // Milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timeout: Option<u128>,
+
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    sticky: bool,
+}
+
+impl NotificationOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn action(mut self, action: NotificationAction) -> Self {
+        self.actions.push(action);
+        self
+    }
+    // Closes the notification after `timeout` instead of the default.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout.as_millis());
+        self
+    }
+    // Keeps the notification open until it is closed or one of its actions is used.
+    pub fn sticky(mut self) -> Self {
+        self.sticky = true;
+        self
+    }
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/**
+ * NotificationAction
+ *
+ * A notification button that fires an htmx request, ex: "Undo" after a delete.
+ * The notification closes once the request has finished.
+ */
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct NotificationAction {
+    label: String,
+    hx: Hx,
+}
+
+impl NotificationAction {
+    pub fn new<S: Into<String>>(label: S, hx: Hx) -> Self {
+        // The button goes away with its notification, so by default nothing is swapped into it.
+        let hx = match hx.attr("hx-target").or(hx.attr("hx-swap")) {
+            Some(_) => hx,
+            None => hx.swap(SwapStrategy::None),
+        };
+
+        Self {
+            label: label.into(),
+            hx,
+        }
+    }
+}
+
+#[props]
+pub struct NotificationActionButtonProps {
+    action: NotificationAction,
+}
+
+#[component]
+pub fn NotificationActionButton(props: NotificationActionButtonProps) -> String {
+    html! {
+        <HtmlElement
+            tag="button"
+            component_name="NotificationActionButton"
+            class="rounded-md bg-white text-sm font-medium text-indigo-600 hover:text-indigo-500 focus:outline-none focus:ring-2 focus:ring-indigo-500 focus:ring-offset-2"
+            attrs=Attrs::from(props.action.hx)
+                .set("type", "button".into())
+                .set("data-notification-action", "true".into())
+            text=props.action.label
+        />
+    }
+}
+
+// #### Notifications from response headers. ###############
+
+// Event handled by Notification.ts.
+pub const NOTIFICATION_REQUEST_EVENT: &str = "yc:notificationRequest";
+
+/**
+ * NotificationRequest
+ *
+ * A notification sent to the client as an `HX-Trigger` event instead of an inline script.
+ * Works with any swap target (or no swap at all) and under a strict Content-Security-Policy.
+ * See `HxResponse::notify` in web-htmx.
+ */
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct NotificationRequest {
+    #[serde(flatten)]
+    message: NotificationMessage,
+
+    #[serde(flatten)]
+    options: NotificationOptions,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "UPPERCASE")]
+enum NotificationMessage {
+    Success { message: String },
+    Error { message: String },
+    Warning { message: String },
+    Info { message: String },
+    Debug { message: String },
+    Generic { title: String, message: String },
+}
+
+impl From<NotificationMessage> for NotificationRequest {
+    fn from(message: NotificationMessage) -> Self {
+        Self {
+            message,
+            options: NotificationOptions::default(),
+        }
+    }
+}
+
+impl From<(axum_flash::Level, String)> for NotificationRequest {
+    fn from((level, message): (axum_flash::Level, String)) -> Self {
+        match level {
+            axum_flash::Level::Success => NotificationMessage::Success { message },
+            axum_flash::Level::Error => NotificationMessage::Error { message },
+            axum_flash::Level::Warning => NotificationMessage::Warning { message },
+            axum_flash::Level::Info => NotificationMessage::Info { message },
+            axum_flash::Level::Debug => NotificationMessage::Debug { message },
+        }
+        .into()
+    }
+}
+
+impl NotificationRequest {
+    pub fn success<S: Into<String>>(message: S) -> Self {
+        NotificationMessage::Success {
+            message: message.into(),
+        }
+        .into()
+    }
+    pub fn error<S: Into<String>>(message: S) -> Self {
+        NotificationMessage::Error {
+            message: message.into(),
+        }
+        .into()
+    }
+    pub fn warning<S: Into<String>>(message: S) -> Self {
+        NotificationMessage::Warning {
+            message: message.into(),
+        }
+        .into()
+    }
+    pub fn info<S: Into<String>>(message: S) -> Self {
+        NotificationMessage::Info {
+            message: message.into(),
+        }
+        .into()
+    }
+    pub fn debug<S: Into<String>>(message: S) -> Self {
+        NotificationMessage::Debug {
+            message: message.into(),
+        }
+        .into()
+    }
+    pub fn generic<T: Into<String>, S: Into<String>>(title: T, message: S) -> Self {
+        NotificationMessage::Generic {
+            title: title.into(),
+            message: message.into(),
+        }
+        .into()
+    }
+    pub fn action(mut self, action: NotificationAction) -> Self {
+        self.options = self.options.action(action);
+        self
+    }
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.options = self.options.timeout(timeout);
+        self
+    }
+    pub fn sticky(mut self) -> Self {
+        self.options = self.options.sticky();
+        self
+    }
+}
+
 // #### Notification components to help you build your own. ###############
 
 #[props]
@@ -213,7 +466,7 @@ pub struct NotificationTransitionProps {
 pub fn NotificationTransition(props: NotificationTransitionProps) -> String {
     html! {
         <Transition
-            class=format!("pointer-events-auto {}", props.class).trim()
+            class=ClassList::from("pointer-events-auto").merge(&props.class)
             enter="transform ease-out duration-300 transition"
             enter_from="translate-y-2 opacity-0 sm:translate-y-0 sm:translate-x-2"
             enter_to="translate-y-0 opacity-100 sm:translate-x-0"
@@ -239,3 +492,138 @@ pub fn NoticationCloseButton() -> String {
         </div>
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notification_request_json() {
+        assert_eq!(
+            serde_json::to_string(&NotificationRequest::success("Saved!")).unwrap(),
+            r#"{"kind":"SUCCESS","message":"Saved!"}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&NotificationRequest::generic("Heads up", "Hello")).unwrap(),
+            r#"{"kind":"GENERIC","title":"Heads up","message":"Hello"}"#
+        );
+        assert_eq!(
+            NotificationRequest::from((axum_flash::Level::Warning, "Careful".to_string())),
+            NotificationRequest::warning("Careful")
+        );
+    }
+
+    #[test]
+    fn test_flash_levels_have_their_own_notification() {
+        assert_eq!(
+            [
+                axum_flash::Level::Success,
+                axum_flash::Level::Error,
+                axum_flash::Level::Warning,
+                axum_flash::Level::Info,
+                axum_flash::Level::Debug,
+            ]
+            .map(flash_notification_fn),
+            [
+                "showSuccessNotification",
+                "showErrorNotification",
+                "showWarningNotification",
+                "showInfoNotification",
+                "showDebugNotification",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_warning_notification_call() {
+        let html = html! {
+            <NotificationPresenter call=NotificationCall::Warning("Disk is <90%> full".into()) />
+        };
+
+        assert!(html.contains(r#"showWarningNotification("Disk is \u003c90%\u003e full")"#));
+    }
+
+    #[tokio::test]
+    async fn test_live_region_has_icons_for_every_level() {
+        let html = html! { <NotificationLiveRegion /> };
+
+        for icon in ["success", "error", "info", "warning", "debug"] {
+            assert!(html.contains(&format!("data-notification-icon=\"{}\"", icon)));
+        }
+    }
+
+    #[test]
+    fn test_notification_request_with_options_json() {
+        let request = NotificationRequest::success("Worker deleted.")
+            .action(NotificationAction::new(
+                "Undo",
+                Hx::post("/workers/1/restore"),
+            ))
+            .timeout(Duration::from_secs(10));
+
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            serde_json::json!({
+                "kind": "SUCCESS",
+                "message": "Worker deleted.",
+                "actions": [{
+                    "label": "Undo",
+                    "hx": { "hx-post": "/workers/1/restore", "hx-swap": "none" },
+                }],
+                "timeout": 10000,
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(NotificationRequest::error("Nope").sticky()).unwrap(),
+            serde_json::json!({ "kind": "ERROR", "message": "Nope", "sticky": true })
+        );
+    }
+
+    #[test]
+    fn test_action_keeps_its_own_target() {
+        let action = NotificationAction::new("View", Hx::get("/workers/1").target("#main"));
+
+        assert_eq!(action.hx.attr("hx-swap"), None);
+    }
+
+    #[tokio::test]
+    async fn test_notification_call_with_options() {
+        let html = html! {
+            <NotificationPresenter
+                call=NotificationCall::Success("Deleted".into())
+                options=NotificationOptions::new().sticky()
+            />
+        };
+
+        assert!(html.contains(r#"showSuccessNotification("Deleted", {"sticky":true})"#));
+    }
+
+    #[tokio::test]
+    async fn test_notification_call_with_options_keeps_parentheses() {
+        let html = html! {
+            <NotificationPresenter
+                call=NotificationCall::Info("Saved".into(), "Tuesday (9am)".into())
+                options=NotificationOptions::new().sticky()
+            />
+        };
+
+        assert!(html.contains(r#"showNotification("Saved", "Tuesday (9am)", {"sticky":true})"#));
+    }
+
+    #[tokio::test]
+    async fn test_simple_notification_renders_actions() {
+        let html = html! {
+            <SimpleNotification
+                icon_svg=IconSvg::Success
+                actions=vec![NotificationAction::new("Undo <it>", Hx::post("/restore"))]
+            />
+        };
+
+        assert!(html.contains("hx-post=\"/restore\""));
+        assert!(html.contains("hx-swap=\"none\""));
+        assert!(
+            html.contains(r#"data-notification-action="true" data-rsx="NotificationActionButton""#)
+        );
+        assert!(html.contains("Undo &lt;it&gt;"));
+    }
+}
@@ -1,10 +1,66 @@
 use std::collections::HashMap;
 
+use super::escape::escape_attribute;
+
+// https://html.spec.whatwg.org/multipage/indices.html#attributes-3
+pub const BOOLEAN_ATTRIBUTES: [&str; 24] = [
+    "allowfullscreen",
+    "async",
+    "autofocus",
+    "autoplay",
+    "checked",
+    "controls",
+    "default",
+    "defer",
+    "disabled",
+    "formnovalidate",
+    "hidden",
+    "inert",
+    "ismap",
+    "itemscope",
+    "loop",
+    "multiple",
+    "muted",
+    "nomodule",
+    "novalidate",
+    "open",
+    "playsinline",
+    "readonly",
+    "required",
+    "selected",
+];
+
+pub fn is_boolean_attribute<S: AsRef<str>>(key: S) -> bool {
+    BOOLEAN_ATTRIBUTES.contains(&key.as_ref().to_ascii_lowercase().as_str())
+}
+
+// Attribute keys can be built at runtime, so anything that could break out of the tag is rejected.
+// https://html.spec.whatwg.org/multipage/syntax.html#attributes-2
+pub fn is_valid_attribute_name<S: AsRef<str>>(key: S) -> bool {
+    let key = key.as_ref();
+
+    !key.is_empty()
+        && !key.chars().any(|c| {
+            c.is_whitespace() || c.is_control() || matches!(c, '"' | '\'' | '>' | '/' | '=' | '<')
+        })
+}
+
 pub fn opt_attr<S: AsRef<str>, T: AsRef<str>>(key: S, val: T) -> String {
-    if val.as_ref().is_empty() {
+    if !is_valid_attribute_name(&key) {
+        String::from("")
+    } else if is_boolean_attribute(&key) {
+        // Boolean attributes are on by presence alone, so "false" has to be dropped entirely.
+        // Other values are kept, some have states of their own (`hidden="until-found"`).
+        match val.as_ref().trim() {
+            "" | "false" => String::from(""),
+            "true" => key.as_ref().to_string(),
+            val if val.eq_ignore_ascii_case(key.as_ref()) => key.as_ref().to_string(),
+            _ => format!("{}=\"{}\"", key.as_ref(), escape_attribute(val)),
+        }
+    } else if val.as_ref().is_empty() {
         String::from("")
     } else {
-        format!("{}=\"{}\"", key.as_ref(), val.as_ref())
+        format!("{}=\"{}\"", key.as_ref(), escape_attribute(val))
     }
 }
 
@@ -49,6 +105,60 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_opt_attr_escapes_quotes() {
+        assert_eq!(
+            opt_attr("title", String::from("say \"hi\" & 'bye'")),
+            String::from("title=\"say &quot;hi&quot; &amp; &#x27;bye&#x27;\"")
+        );
+    }
+
+    #[test]
+    fn test_opt_attr_escapes_angle_brackets() {
+        assert_eq!(
+            opt_attr("foo", String::from("\"><script>alert(1)</script>")),
+            String::from("foo=\"&quot;&gt;&lt;script&gt;alert(1)&lt;/script&gt;\"")
+        );
+    }
+
+    #[test]
+    fn test_opt_attr_with_boolean_attribute() {
+        assert_eq!(opt_attr("disabled", "disabled"), String::from("disabled"));
+        assert_eq!(opt_attr("required", "true"), String::from("required"));
+    }
+
+    #[test]
+    fn test_opt_attr_with_boolean_attribute_turned_off() {
+        assert_eq!(opt_attr("checked", ""), String::from(""));
+        assert_eq!(opt_attr("checked", "false"), String::from(""));
+    }
+
+    #[test]
+    fn test_opt_attr_with_boolean_attribute_keeps_other_values() {
+        assert_eq!(opt_attr("hidden", "hidden"), String::from("hidden"));
+        assert_eq!(
+            opt_attr("hidden", "until-found"),
+            String::from("hidden=\"until-found\"")
+        );
+    }
+
+    #[test]
+    fn test_opt_attrs_with_boolean_and_valued_attributes() {
+        let attrs = opt_attrs(HashMap::from([
+            ("readonly", String::from("readonly")),
+            ("multiple", String::from("false")),
+            ("name", String::from("notes")),
+        ]));
+
+        assert_eq!(attrs, String::from("name=\"notes\" readonly"));
+    }
+
+    #[test]
+    fn test_opt_attr_with_invalid_key() {
+        assert_eq!(opt_attr("onclick=\"alert(1)\" x", "y"), String::from(""));
+        assert_eq!(opt_attr("data-<b>", "y"), String::from(""));
+    }
+
     #[test]
     fn test_opt_attrs_with_empty_map() {
         assert_eq!(opt_attrs(HashMap::<&str, &str>::new()), String::from(""));
@@ -1,3 +1,4 @@
+use super::escape::escape_text;
 use super::html_element::HtmlElement;
 use rscx::{component, html, props};
 use web_macros::*;
@@ -20,7 +21,7 @@ pub fn PageHeaderToolbar(props: PageHeaderToolbarProps) -> String {
         >
             <div class="mt-2 md:flex md:items-center md:justify-between">
                 <div class="min-w-0 flex-1">
-                    <h2 class="text-2xl font-bold leading-7 text-gray-900 sm:truncate sm:text-3xl sm:tracking-tight">{props.title}</h2>
+                    <h2 class="text-2xl font-bold leading-7 text-gray-900 sm:truncate sm:text-3xl sm:tracking-tight">{escape_text(&props.title)}</h2>
                 </div>
                 <div class="mt-4 flex flex-shrink-0 gap-2 md:ml-4 md:mt-0">
                     {props.buttons}
@@ -4,6 +4,8 @@ use typed_builder::TypedBuilder;
 use web_macros::*;
 
 use super::attrs::Attrs;
+use super::class_list::ClassList;
+use super::escape::escape_text;
 use super::html_element::HtmlElement;
 use super::opt_attrs::opt_attrs;
 use super::transition::Transition;
@@ -57,12 +59,12 @@ pub struct PopupMenuProps {
 #[component]
 pub fn PopupMenu(props: PopupMenuProps) -> String {
     html! {
-        <Toggle class=format!("relative {}", props.class).trim()>
+        <Toggle class=ClassList::from("relative").merge(&props.class)>
             <div>
                 <button
                     type="button"
                     id=format!("{}-button", &props.id)
-                    class=format!("relative {}", props.button_class).trim()
+                    class=ClassList::from("relative").merge(&props.button_class).to_string()
                     aria-expanded="false"
                     aria-haspopup="true"
                     data-toggle-action="click"
@@ -119,8 +121,8 @@ pub fn MenuItem(props: MenuItemProps) -> String {
             tabindex="-1"
             attrs=spread_attrs!(props | omit(class))
         >
-            {props.title}
-            <span class="sr-only">{props.sr_suffix}</span>
+            {escape_text(&props.title)}
+            <span class="sr-only">{escape_text(&props.sr_suffix)}</span>
         </HtmlElement>
     }
 }
@@ -157,11 +159,44 @@ pub fn Menu(props: MenuProps) -> String {
                         id={format!("{}-item-{}", &props.id, i)}
                         {opt_attrs(attrs.to_hashmap())}
                     >
-                        {label}
-                        <span class="sr-only">", "{sr_suffix}</span>
+                        {escape_text(&label)}
+                        <span class="sr-only">", "{escape_text(&sr_suffix)}</span>
                     </a>
                 }
             },
         )
         .collect_fragment()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_menu_escapes_labels_and_attrs() {
+        let html = html! {
+            <Menu
+                id="menu"
+                links=vec![
+                    MenuLink::from((
+                        "<b>Tom & Jerry</b>".to_string(),
+                        "/search?q=\"><script>".to_string(),
+                    )),
+                ]
+            />
+        };
+
+        assert!(html.contains("href=\"/search?q=&quot;&gt;&lt;script&gt;\""));
+        assert!(html.contains(">&lt;b&gt;Tom &amp; Jerry&lt;/b&gt;<span"));
+    }
+
+    #[tokio::test]
+    async fn test_menu_item_escapes_title() {
+        let html = html! {
+            <MenuItem title="Fish & \"Chips\"" sr_suffix="<i>" />
+        };
+
+        assert!(html.contains(">Fish &amp; \"Chips\"<span"));
+        assert!(html.contains(">&lt;i&gt;</span></a>"));
+    }
+}
@@ -1,5 +1,7 @@
 use rscx::{component, html, props, CollectFragment};
 
+use super::class_list::ClassList;
+use super::escape::escape_text;
 use super::html_element::HtmlElement;
 
 use web_macros::*;
@@ -90,11 +92,11 @@ fn TableHeadingsRow(headings: TableHeadings) -> String {
 
                 match heading {
                     TableHeading::Title(heading) => html! {
-                        <th scope="col" class=th_class>{heading}</th>
+                        <th scope="col" class=th_class>{escape_text(heading)}</th>
                     },
                     TableHeading::Empty(sr_only_text) => html! {
                         <th scope="col" class=format!("relative {}", th_class)>
-                            <span class="sr-only">{sr_only_text}</span>
+                            <span class="sr-only">{escape_text(sr_only_text)}</span>
                         </th>
                     },
                 }
@@ -130,10 +132,10 @@ pub fn ActionLink(props: ActionLinkProps) -> String {
     html! {
         <HtmlElement
             tag="a"
-            class=format!("cursor-pointer text-indigo-600 hover:text-indigo-900, {}", props.class).trim()
+            class=ClassList::from("cursor-pointer text-indigo-600 hover:text-indigo-900").merge(&props.class)
             attrs=spread_attrs!(props | omit(class))
         >
-            {props.children}<span class="sr-only">{props.sr_text}</span>
+            {props.children}<span class="sr-only">{escape_text(&props.sr_text)}</span>
         </HtmlElement>
     }
 }
@@ -0,0 +1,56 @@
+use rscx::{component, html, props, CollectFragmentAsync};
+
+use super::csp::Script;
+use crate::vendor::{VendorScript, VENDOR_SCRIPTS};
+
+// `VENDOR_INTEGRITY: &[(path, integrity)]`, generated by build.rs.
+include!(concat!(env!("OUT_DIR"), "/vendor_integrity.rs"));
+
+fn integrity(script: &VendorScript) -> Option<&'static str> {
+    let path = script.path();
+
+    VENDOR_INTEGRITY
+        .iter()
+        .find(|(p, _)| *p == path)
+        .map(|(_, integrity)| *integrity)
+}
+
+/**
+ * VendorScripts
+ *
+ * A `<script>` for each of the vendored scripts (see `vendor.rs`), with its integrity hash.
+ */
+#[component]
+pub fn VendorScripts() -> String {
+    html! {
+        {
+            VENDOR_SCRIPTS
+                .iter()
+                .map(|script| async move {
+                    html! {
+                        <Script
+                            src=format!("/client/{}", script.path())
+                            integrity=integrity(script).unwrap_or_default()
+                            crossorigin="anonymous"
+                        />
+                    }
+                })
+                .collect_fragment_async()
+                .await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_vendor_scripts_are_versioned() {
+        let html = html! { <VendorScripts /> };
+
+        assert!(html.contains(r#"src="/client/vendor/htmx.org@1.9.9/dist/htmx.min.js""#));
+        assert!(html.find("htmx.min.js") < html.find("loading-states.js"));
+        assert!(!html.contains("unpkg.com"));
+    }
+}
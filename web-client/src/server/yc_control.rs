@@ -1,5 +1,8 @@
+use super::attrs::Attrs;
+use super::csp::Script;
 use super::html_element::HtmlElement;
 use rscx::{component, html, props};
+use serde::Serialize;
 use web_macros::*;
 
 #[html_element]
@@ -18,7 +21,7 @@ pub fn YcControl(props: YcControlProps) -> String {
             attrs=spread_attrs!(props).set("data-yc-control", props.control)
         >
             {props.children}
-            <script>"YcControls.attach(document.currentScript.parentElement);"</script>
+            <Script>"YcControls.attach(document.currentScript.parentElement);"</Script>
         </HtmlElement>
     }
 }
@@ -50,7 +53,7 @@ pub struct YcControlJsApiProps {
 #[component]
 pub fn YcControlJsApi(props: YcControlJsApiProps) -> String {
     html! {
-        <script>
+        <Script>
             {format!(
                 r#"
                     (function(callerScript) {{
@@ -61,6 +64,48 @@ pub fn YcControlJsApi(props: YcControlJsApiProps) -> String {
                 "#,
                 props.call,
             )}
-        </script>
+        </Script>
+    }
+}
+
+/**
+ * yc_click
+ *
+ * Calls `YcControls.<function>(...args)` when the element is clicked. The CSP blocks inline
+ * `onclick` handlers, use this instead. `args` is serialized as a json array, `()` for none.
+ *
+ * <PrimaryButton attrs=yc_click("showSuccessNotification", &["Saved!"])>Save</PrimaryButton>
+ */
+pub fn yc_click<T: Serialize + ?Sized>(function: &str, args: &T) -> Attrs {
+    let args = serde_json::to_value(args).expect("click arguments serialize to json");
+
+    Attrs::with("data-yc-click", function.into()).set_if(
+        "data-yc-args",
+        args.to_string(),
+        !args.is_null(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_yc_click() {
+        let attrs = yc_click("showNotification", &["Saved", "It's \"done\""]);
+
+        assert_eq!(
+            attrs.get("data-yc-click"),
+            Some("showNotification".to_string())
+        );
+        assert_eq!(
+            attrs.get("data-yc-args"),
+            Some(r#"["Saved","It's \"done\""]"#.to_string())
+        );
+        assert_eq!(
+            String::from(attrs),
+            r#"data-yc-args="[&quot;Saved&quot;,&quot;It&#x27;s \&quot;done\&quot;&quot;]" data-yc-click="showNotification""#
+        );
+        assert_eq!(yc_click("historyBack", &()).get("data-yc-args"), None);
     }
 }
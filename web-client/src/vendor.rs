@@ -0,0 +1,41 @@
+/*
+ * Vendored scripts
+ *
+ * Third party scripts we serve ourselves (from `/client/vendor`) instead of a CDN. Each one is
+ * an npm dependency pinned in package.json, build.rs copies it out of node_modules and hashes it
+ * for subresource integrity.
+ *
+ * This file is also included by build.rs, keep it free of crate imports.
+ */
+
+pub struct VendorScript {
+    pub package: &'static str,
+    pub version: &'static str,
+    pub file: &'static str,
+}
+
+impl VendorScript {
+    // Versioned path under `/client`, ex: `vendor/htmx.org@1.9.9/dist/htmx.min.js`.
+    pub fn path(&self) -> String {
+        format!("vendor/{}@{}/{}", self.package, self.version, self.file)
+    }
+}
+
+// In load order, extensions after htmx.
+pub const VENDOR_SCRIPTS: &[VendorScript] = &[
+    VendorScript {
+        package: "htmx.org",
+        version: "1.9.9",
+        file: "dist/htmx.min.js",
+    },
+    VendorScript {
+        package: "htmx.org",
+        version: "1.9.9",
+        file: "dist/ext/loading-states.js",
+    },
+    VendorScript {
+        package: "htmx.org",
+        version: "1.9.9",
+        file: "dist/ext/response-targets.js",
+    },
+];
@@ -1,8 +1,11 @@
 pub mod appshell;
 pub mod empty_state;
+pub mod login_form;
 pub mod logo;
 pub mod nav;
 pub mod not_found_message;
+pub mod notification_center;
+pub mod oob_swap;
 pub mod page;
 pub mod page_content;
 pub mod simple_form;
@@ -1,6 +1,6 @@
 use super::nav::Nav;
 use rscx::{component, html, props};
-use web_client::server::page_header::PageHeaderToolbar;
+use web_client::server::{escape::escape_text, page_header::PageHeaderToolbar};
 
 pub enum PageHeader {
     None,
@@ -64,7 +64,7 @@ fn MainContent(props: MainContentProps) -> String {
                     PageHeader::None => html! {},
                     PageHeader::Title(title) => html! {
                         <header class="mx-auto max-w-7xl px-4 sm:px-6 lg:px-8">
-                            <h1 class="text-3xl font-bold leading-tight tracking-tight text-gray-900">{title}</h1>
+                            <h1 class="text-3xl font-bold leading-tight tracking-tight text-gray-900">{escape_text(title)}</h1>
                         </header>
                     },
                     PageHeader::Toolbar { title, buttons } => html! {
@@ -0,0 +1,80 @@
+use rscx::{component, html, props};
+use web_client::server::{
+    attrs::Attrs,
+    form::{Button, GridCell, GridLayout, Label, TextInput},
+};
+
+use crate::routes;
+
+/**
+ * LoginForm
+ *
+ * Username and password, posted to `routes::login()`. `next` is where to go once signed in.
+ */
+#[props]
+pub struct LoginFormProps {
+    #[builder(setter(into), default)]
+    next: String,
+}
+
+#[component]
+pub fn LoginForm(props: LoginFormProps) -> String {
+    html! {
+        <form method="post" action=routes::login() class="max-w-sm">
+            <input type="hidden" name="next" value=props.next />
+            <GridLayout>
+                <GridCell>
+                    <Label for_input="username">Username</Label>
+                    <TextInput
+                        name="username"
+                        autocomplete="username"
+                        attrs=Attrs::default().set_bool("required", true)
+                    />
+                </GridCell>
+                <GridCell>
+                    <Label for_input="password">Password</Label>
+                    <TextInput
+                        name="password"
+                        input_type="password"
+                        autocomplete="current-password"
+                        attrs=Attrs::default().set_bool("required", true)
+                    />
+                </GridCell>
+                <GridCell>
+                    <div class="flex justify-end">
+                        <Button kind="submit">Sign in</Button>
+                    </div>
+                </GridCell>
+            </GridLayout>
+        </form>
+    }
+}
+
+/**
+ * LogoutForm
+ *
+ * Signing out is a POST, so a link (or a prefetch) can't sign anyone out.
+ */
+#[component]
+pub fn LogoutForm() -> String {
+    html! {
+        <form method="post" action=routes::logout()>
+            <Button kind="submit">Sign out</Button>
+        </form>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_login_form_keeps_next() {
+        let html = html! { <LoginForm next="/playground?tab=\"auth\"" /> };
+
+        assert!(
+            html.contains(r#"name="next" value="&#x2F;playground&#x3F;tab&#x3D;&quot;auth&quot;""#)
+        );
+        assert!(html.contains(r#"name="password" required type="password""#));
+    }
+}
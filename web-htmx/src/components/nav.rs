@@ -5,6 +5,7 @@ use web_client::server::modal::modal_target;
 use web_client::server::popup_menu::{Menu, MenuLink, PopupMenu};
 
 use crate::components::logo::Logo;
+use crate::components::notification_center::NotificationBell;
 use crate::routes;
 
 #[component]
@@ -12,6 +13,12 @@ pub fn Nav() -> String {
     let ctx: crate::context::Context =
         crate::context::context().expect("Unable to retrieve htmx context.");
     let nav_links: Vec<(&str, String)> = vec![("Home", routes::home())];
+    // The notifications are the signed in user's, there are none to show otherwise.
+    let bell = if ctx.current_user.is_some() {
+        html! { <NotificationBell /> }
+    } else {
+        String::new()
+    };
 
     html! {
         <nav class="border-b border-gray-200 bg-white">
@@ -54,7 +61,8 @@ pub fn Nav() -> String {
                         </div>
                     </div>
                     <div class="hidden sm:ml-6 sm:flex sm:items-center">
-                        <ProfileDropdown />
+                        {bell.clone()}
+                        <ProfileDropdown signed_in=ctx.current_user.is_some() />
                     </div>
 
                     <div class="-mr-2 flex items-center sm:hidden">
@@ -115,13 +123,9 @@ pub fn Nav() -> String {
                             <div class="text-base font-medium text-gray-800">Tom Cook</div>
                             <div class="text-sm font-medium text-gray-500">tom@example.com</div>
                         </div>
-                        <button type="button" class="relative ml-auto flex-shrink-0 rounded-full bg-white p-1 text-gray-400 hover:text-gray-500 focus:outline-none focus:ring-2 focus:ring-indigo-500 focus:ring-offset-2">
-                            <span class="absolute -inset-1.5"></span>
-                            <span class="sr-only">View notifications</span>
-                            <svg class="h-6 w-6" fill="none" viewBox="0 0 24 24" stroke-width="1.5" stroke="currentColor" aria-hidden="true">
-                                <path stroke-linecap="round" stroke-linejoin="round" d="M14.857 17.082a23.848 23.848 0 005.454-1.31A8.967 8.967 0 0118 9.75v-.7V9A6 6 0 006 9v.75a8.967 8.967 0 01-2.312 6.022c1.733.64 3.56 1.085 5.455 1.31m5.714 0a24.255 24.255 0 01-5.714 0m5.714 0a3 3 0 11-5.714 0" />
-                            </svg>
-                        </button>
+                        <div class="ml-auto flex-shrink-0">
+                            {bell}
+                        </div>
                     </div>
                     <div class="mt-3 space-y-1">
                         // TODO! User Profile Links
@@ -132,8 +136,19 @@ pub fn Nav() -> String {
     }
 }
 
+#[props]
+struct ProfileDropdownProps {
+    signed_in: bool,
+}
+
 #[component]
-fn ProfileDropdown() -> String {
+fn ProfileDropdown(props: ProfileDropdownProps) -> String {
+    let link = if props.signed_in {
+        ("Sign out".to_string(), routes::logout())
+    } else {
+        ("Sign in".to_string(), routes::login())
+    };
+
     html! {
         <PopupMenu
             id="user-nav-popupmenu"
@@ -149,8 +164,24 @@ fn ProfileDropdown() -> String {
         >
             <Menu
                 id="user-nav-menu"
-                links=vec![] // Put profile links here
+                links=vec![link.into()]
             />
         </PopupMenu>
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_profile_dropdown_offers_sign_in_or_sign_out() {
+        let signed_in = html! { <ProfileDropdown signed_in=true /> };
+        assert!(signed_in.contains("Sign out"));
+        assert!(!signed_in.contains("Sign in"));
+
+        let anonymous = html! { <ProfileDropdown signed_in=false /> };
+        assert!(anonymous.contains("Sign in"));
+        assert!(!anonymous.contains("Sign out"));
+    }
+}
@@ -0,0 +1,257 @@
+use rscx::{component, html, props, CollectFragmentAsync};
+
+use notifications_service::models::Notification;
+use web_client::server::{
+    button::{ButtonSize, SecondaryButton},
+    escape::escape_text,
+    flyout::Flyout,
+    html_element::HtmlElement,
+    hx::{Hx, SwapStrategy, Trigger},
+};
+
+use crate::routes;
+
+// Sent (as `HX-Trigger`) whenever a user's notifications change, the bell reloads its badge.
+pub const NOTIFICATIONS_CHANGED_EVENT: &str = "yc:notificationsChanged";
+
+const NOTIFICATION_LIST_ID: &str = "notification-center-list";
+
+/**
+ * NotificationBell
+ *
+ * The nav bar bell. Loads the unread badge after the page, and again on
+ * `NOTIFICATIONS_CHANGED_EVENT`, so pages don't have to look up notifications.
+ */
+#[component]
+pub fn NotificationBell() -> String {
+    html! {
+        <HtmlElement
+            component_name="NotificationBell"
+            class="relative"
+            hx=Hx::get(routes::notifications_bell())
+                .trigger(Trigger::load())
+                .trigger(Trigger::event(NOTIFICATIONS_CHANGED_EVENT).from_element("body"))
+        >
+            <NotificationBellButton unread_count=0 />
+        </HtmlElement>
+    }
+}
+
+#[props]
+pub struct NotificationBellButtonProps {
+    unread_count: usize,
+}
+
+#[component]
+pub fn NotificationBellButton(props: NotificationBellButtonProps) -> String {
+    let badge = match props.unread_count {
+        0 => String::new(),
+        count @ 1..=99 => count.to_string(),
+        _ => "99+".into(),
+    };
+
+    html! {
+        <HtmlElement
+            tag="button"
+            component_name="NotificationBellButton"
+            class="relative rounded-full bg-white p-1 text-gray-400 hover:text-gray-500 focus:outline-none focus:ring-2 focus:ring-indigo-500 focus:ring-offset-2"
+            hx=Hx::get(routes::notifications())
+                .target("body")
+                .swap(SwapStrategy::BeforeEnd)
+        >
+            <span class="absolute -inset-1.5"></span>
+            <span class="sr-only">
+                {match props.unread_count {
+                    0 => "View notifications".to_string(),
+                    count => format!("View notifications ({} unread)", count),
+                }}
+            </span>
+            <svg class="h-6 w-6" fill="none" viewBox="0 0 24 24" stroke-width="1.5" stroke="currentColor" aria-hidden="true">
+                <path stroke-linecap="round" stroke-linejoin="round" d="M14.857 17.082a23.848 23.848 0 005.454-1.31A8.967 8.967 0 0118 9.75v-.7V9A6 6 0 006 9v.75a8.967 8.967 0 01-2.312 6.022c1.733.64 3.56 1.085 5.455 1.31m5.714 0a24.255 24.255 0 01-5.714 0m5.714 0a3 3 0 11-5.714 0" />
+            </svg>
+            {
+                if badge.is_empty() {
+                    String::new()
+                } else {
+                    html! {
+                        <span
+                            class="absolute -right-1 -top-1 flex h-4 min-w-[1rem] items-center justify-center rounded-full bg-red-500 px-1 text-[10px] font-semibold text-white"
+                            aria-hidden="true"
+                            data-notification-badge
+                        >
+                            {badge}
+                        </span>
+                    }
+                }
+            }
+        </HtmlElement>
+    }
+}
+
+/**
+ * NotificationCenterFlyout
+ *
+ * The signed in user's notifications, newest first.
+ */
+#[props]
+pub struct NotificationCenterFlyoutProps {
+    notifications: Vec<Notification>,
+}
+
+#[component]
+pub fn NotificationCenterFlyout(props: NotificationCenterFlyoutProps) -> String {
+    html! {
+        <Flyout title="Notifications">
+            <div class="flex justify-end">
+                <SecondaryButton
+                    size=ButtonSize::Xs
+                    hx=Hx::post(routes::notifications_read_all())
+                        .target(format!("#{}", NOTIFICATION_LIST_ID))
+                        .swap(SwapStrategy::OuterHtml)
+                >
+                    Mark all as read
+                </SecondaryButton>
+            </div>
+            <NotificationList notifications=props.notifications />
+        </Flyout>
+    }
+}
+
+#[props]
+pub struct NotificationListProps {
+    notifications: Vec<Notification>,
+}
+
+#[component]
+pub fn NotificationList(props: NotificationListProps) -> String {
+    if props.notifications.is_empty() {
+        return html! {
+            <div id=NOTIFICATION_LIST_ID class="py-10 text-center text-sm text-gray-500">
+                "You're all caught up."
+            </div>
+        };
+    }
+
+    html! {
+        <ul id=NOTIFICATION_LIST_ID role="list" class="mt-4 divide-y divide-gray-100">
+            {
+                props
+                    .notifications
+                    .into_iter()
+                    .map(|notification| async move {
+                        html! { <NotificationListItem notification=notification /> }
+                    })
+                    .collect_fragment_async()
+                    .await
+            }
+        </ul>
+    }
+}
+
+#[props]
+struct NotificationListItemProps {
+    notification: Notification,
+}
+
+#[component]
+fn NotificationListItem(props: NotificationListItemProps) -> String {
+    let notification = props.notification;
+    let is_read = notification.is_read();
+
+    html! {
+        <li class="flex gap-x-3 py-4" data-notification-read=is_read.to_string()>
+            <span
+                class=if is_read { "mt-1.5 h-2 w-2 flex-none rounded-full" } else { "mt-1.5 h-2 w-2 flex-none rounded-full bg-indigo-500" }
+                aria-hidden="true"
+            ></span>
+            <div class="min-w-0 flex-auto">
+                <p class="text-sm font-semibold leading-6 text-gray-900">
+                    {match &notification.url {
+                        Some(url) => html! { <a href=url.clone() class="hover:underline">{escape_text(&notification.title)}</a> },
+                        None => escape_text(&notification.title),
+                    }}
+                </p>
+                <p class="mt-1 text-sm leading-5 text-gray-600">{escape_text(&notification.message)}</p>
+                <p class="mt-1 text-xs leading-5 text-gray-400">
+                    <time datetime=notification.created_at.to_rfc3339()>
+                        {notification.created_at.format("%b %-d, %-I:%M %p").to_string()}
+                    </time>
+                </p>
+            </div>
+            {
+                if is_read {
+                    String::new()
+                } else {
+                    html! {
+                        <SecondaryButton
+                            size=ButtonSize::Xs
+                            class="self-start"
+                            hx=Hx::post(routes::notification_read(&notification.id))
+                                .target(format!("#{}", NOTIFICATION_LIST_ID))
+                                .swap(SwapStrategy::OuterHtml)
+                        >
+                            Mark as read
+                        </SecondaryButton>
+                    }
+                }
+            }
+        </li>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn notification(id: &str, read: bool) -> Notification {
+        Notification {
+            id: id.into(),
+            user_id: "ann".into(),
+            title: "<b>Shift</b> changed".into(),
+            message: "Tuesday starts at 9am.".into(),
+            url: None,
+            created_at: Utc::now(),
+            read_at: if read { Some(Utc::now()) } else { None },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bell_badge() {
+        let html = html! { <NotificationBellButton unread_count=0 /> };
+        assert!(!html.contains("data-notification-badge"));
+
+        let html = html! { <NotificationBellButton unread_count=3 /> };
+        assert!(html.contains("data-notification-badge"));
+        assert!(html.contains("View notifications (3 unread)"));
+
+        let html = html! { <NotificationBellButton unread_count=120 /> };
+        assert!(html.contains(">99+</span>"));
+    }
+
+    #[tokio::test]
+    async fn test_bell_reloads_when_notifications_change() {
+        let html = html! { <NotificationBell /> };
+
+        assert!(html.contains(r#"hx-get="/notifications/bell""#));
+        assert!(html.contains(r#"hx-trigger="load, yc:notificationsChanged from:body""#));
+    }
+
+    #[tokio::test]
+    async fn test_only_unread_notifications_can_be_marked_read() {
+        let html = html! {
+            <NotificationList notifications=vec![notification("1", false), notification("2", true)] />
+        };
+
+        assert!(html.contains(r#"hx-post="/notifications/1/read""#));
+        assert!(!html.contains(r#"hx-post="/notifications/2/read""#));
+        assert!(html.contains("&lt;b&gt;Shift&lt;/b&gt; changed"));
+    }
+
+    #[tokio::test]
+    async fn test_empty_notification_list() {
+        let html = html! { <NotificationList notifications=vec![] /> };
+
+        assert!(html.contains("all caught up"));
+    }
+}
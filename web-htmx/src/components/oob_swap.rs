@@ -0,0 +1,85 @@
+use rscx::{component, html, props};
+use web_client::server::{html_element::HtmlElement, hx::SwapStrategy};
+use web_macros::{html_element, spread_attrs};
+
+/**
+ * OobSwap
+ *
+ * Out of band content, swapped into the page next to the main response.
+ * https://htmx.org/attributes/hx-swap-oob/
+ *
+ * Without a `target`, the element on the page with the same `id` is swapped:
+ *
+ * <OobSwap id="workers-count">{count}</OobSwap>
+ *
+ * With a `target`, the children are swapped into every element matching the selector:
+ *
+ * <OobSwap strategy=SwapStrategy::BeforeEnd target="#notifications">...</OobSwap>
+ */
+#[html_element]
+pub struct OobSwapProps {
+    #[builder(default=SwapStrategy::OuterHtml)]
+    strategy: SwapStrategy,
+
+    #[builder(setter(into), default)]
+    target: String,
+
+    #[builder(setter(into), default=String::from("div"))]
+    tag: String,
+
+    #[builder(default)]
+    children: String,
+}
+
+#[component]
+pub fn OobSwap(props: OobSwapProps) -> String {
+    let swap_oob = match props.target.as_str() {
+        "" => props.strategy.to_string(),
+        target => format!("{}:{}", props.strategy, target),
+    };
+
+    html! {
+        <HtmlElement
+            tag=props.tag
+            component_name="OobSwap"
+            attrs=spread_attrs!(props | omit(hx_swap_oob)).set("hx-swap-oob", swap_oob)
+        >
+            {props.children}
+        </HtmlElement>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_swaps_element_by_id() {
+        let html = html! {
+            <OobSwap id="workers-count" tag="span">42</OobSwap>
+        };
+
+        assert_eq!(
+            html,
+            String::from(
+                "<span data-rsx=\"OobSwap\" hx-swap-oob=\"outerHTML\" id=\"workers-count\">42</span>"
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_swaps_into_target() {
+        let html = html! {
+            <OobSwap strategy=SwapStrategy::BeforeEnd target="#notifications">
+                <p>Saved!</p>
+            </OobSwap>
+        };
+
+        assert_eq!(
+            html,
+            String::from(
+                "<div data-rsx=\"OobSwap\" hx-swap-oob=\"beforeend:#notifications\"><p>Saved!</p></div>"
+            )
+        );
+    }
+}
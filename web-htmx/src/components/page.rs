@@ -1,7 +1,12 @@
 use super::appshell::AppShell;
 pub use super::appshell::PageHeader;
 use rscx::{component, html, props};
-use web_client::server::{modal::ModalLiveRegion, notification::NotificationLiveRegion};
+use web_client::server::{
+    csp::Script,
+    modal::ModalLiveRegion,
+    notification::{NotificationFlashes, NotificationLiveRegion},
+    vendor_scripts::VendorScripts,
+};
 use web_client::HtmlLayout;
 
 #[props]
@@ -26,16 +31,8 @@ pub fn PageLayout(props: PageLayoutProps) -> String {
         <HtmlLayout
             head_scripts={
                 html! {
-                    // Use unminified source for debugging.
-                    // <script src="https://unpkg.com/htmx.org@1.9.9/dist/htmx.js"></script>
-                    <script
-                        src="https://unpkg.com/htmx.org@1.9.9"
-                        integrity="sha384-QFjmbokDn2DjBjq+fM+8LUIVrAgqcNW2s0PjAxHETgRn9l4fvX31ZxDxvwQnyMOX"
-                        crossorigin="anonymous"
-                    ></script>
-                    <script src="https://unpkg.com/htmx.org/dist/ext/loading-states.js"></script>
-                    <script src="https://unpkg.com/htmx.org/dist/ext/response-targets.js"></script>
-                    <script>{
+                    <VendorScripts />
+                    <Script>{
                         r#"
                         htmx.on("htmx:sendError", function() {
                             YcControls.showErrorNotification("Network Error!");
@@ -59,7 +56,7 @@ pub fn PageLayout(props: PageLayoutProps) -> String {
                             });
                         });
                         "#
-                    }</script>
+                    }</Script>
                 }
             }
         >
@@ -69,6 +66,7 @@ pub fn PageLayout(props: PageLayoutProps) -> String {
                 </main>
             </AppShell>
             <ModalProxy />
+            <NotificationFlashes flashes=ctx.flashes />
             <div hx-history-elt>
                 <NotificationLiveRegion />
                 <ModalLiveRegion />
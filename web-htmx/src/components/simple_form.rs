@@ -1,8 +1,8 @@
 use rscx::{component, html, props};
 use serde::Deserialize;
 use web_client::server::{
-    attrs::Attrs,
     form::{Button, GridCell, GridLayout, Label, TextInput},
+    yc_control::yc_click,
 };
 
 #[derive(Default, Deserialize, Debug)]
@@ -59,8 +59,8 @@ pub fn SimpleForm(props: SimpleFormProps) -> String {
                         <GridCell>
                             <div class="mt-6 flex items-center justify-end gap-x-6">
                                 <Button
-                                    onclick="history.go(-1)"
-                                    attrs=Attrs::with("data-toggle-action", "close".into())
+                                    attrs=yc_click("historyBack", &())
+                                        .set("data-toggle-action", "close".into())
                                 >
                                     Cancel
                                 </Button>
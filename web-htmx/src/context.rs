@@ -1,20 +1,30 @@
 use axum::{
+    async_trait,
     body::Body,
-    extract::{Query, State},
-    http::Request,
+    extract::{FromRequestParts, Query, State},
+    http::{header::CONTENT_TYPE, request::Parts, Request, StatusCode},
     middleware::Next,
-    response::Response,
+    response::{IntoResponse, Response},
 };
+use axum_flash::IncomingFlashes;
 use axum_login::{tower_sessions::Session, AuthSession};
 use std::{collections::HashMap, future::Future};
+use web_client::server::csp::provide_nonce;
 
-use crate::state::WebHtmxState;
+use crate::{csp::CspNonce, htmx::request::HxRequest, state::WebHtmxState};
 
 #[derive(Clone)]
 pub struct Context {
     pub page_url: String,
     pub page_query_params: HashMap<String, String>,
     pub is_partial_request: bool,
+    pub htmx: HxRequest,
+    // Empty without the csp layer.
+    pub csp_nonce: String,
+    // Shown by `PageLayout`.
+    pub flashes: IncomingFlashes,
+    // None when no one is signed in.
+    pub current_user: Option<CurrentUser>,
 }
 
 tokio::task_local! {
@@ -24,22 +34,49 @@ tokio::task_local! {
 pub async fn provide_context_layer(
     State(state): State<WebHtmxState>,
     session: Session,
+    flashes: IncomingFlashes,
     request: Request<Body>,
     next: Next,
 ) -> Response {
     let Query(query_params): Query<HashMap<String, String>> =
         Query::try_from_uri(request.uri()).unwrap();
 
-    let is_partial_request = request.headers().contains_key("Hx-Request");
+    let htmx = HxRequest::from_headers(request.headers());
+    let csp_nonce = request
+        .extensions()
+        .get::<CspNonce>()
+        .map(|CspNonce(nonce)| nonce.clone())
+        .unwrap_or_default();
 
     let context = Context {
         page_url: request.uri().path().to_string(),
         page_query_params: query_params,
-        is_partial_request,
+        is_partial_request: htmx.is_partial(),
+        htmx,
+        csp_nonce: csp_nonce.clone(),
+        flashes: flashes.clone(),
+        current_user: request.extensions().get::<CurrentUser>().cloned(),
     };
+    let is_partial_request = context.is_partial_request;
 
-    // Set the context for this request.
-    provide_context(context, next.run(request)).await
+    // Set the context for this request, components rendering `<script>`s get the nonce too.
+    let response = provide_nonce(csp_nonce, provide_context(context, next.run(request))).await;
+
+    // Flashes stay until a page has shown them (redirects pass them on).
+    if !flashes.is_empty() && !is_partial_request && is_html_page(&response) {
+        return (flashes, response).into_response();
+    }
+
+    response
+}
+
+fn is_html_page(response: &Response) -> bool {
+    response.status() == StatusCode::OK
+        && response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.starts_with("text/html"))
 }
 
 pub async fn provide_context<F: Future<Output = O>, O>(context: Context, f: F) -> O {
@@ -49,3 +86,30 @@ pub async fn provide_context<F: Future<Output = O>, O>(context: Context, f: F) -
 pub fn context() -> Option<Context> {
     CONTEXT.try_with(|c| c.clone()).ok()
 }
+
+/**
+ * CurrentUser
+ *
+ * The signed in user. The app's auth layer adds this to the request extensions (see main),
+ * so web-htmx doesn't depend on a particular auth backend.
+ *
+ * Extracting `CurrentUser` responds with 401 when no one is signed in,
+ * use `Option<CurrentUser>` for pages that work either way.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct CurrentUser {
+    pub id: String,
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for CurrentUser {
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<CurrentUser>()
+            .cloned()
+            .ok_or(StatusCode::UNAUTHORIZED)
+    }
+}
@@ -0,0 +1,150 @@
+use axum::{
+    body::Body,
+    extract::State,
+    http::{
+        header::{CONTENT_SECURITY_POLICY, CONTENT_SECURITY_POLICY_REPORT_ONLY},
+        HeaderValue, Request,
+    },
+    middleware::Next,
+    response::Response,
+};
+
+// Replaced with the request's nonce in directive values.
+pub const NONCE: &str = "{nonce}";
+
+/**
+ * ContentSecurityPolicy
+ *
+ * Sent with every response by `csp_layer`, with a new nonce for each request. The nonce is
+ * available from `Context` and is added to every `Script` (see `web_client::server::csp`),
+ * so pages work without `'unsafe-inline'` scripts.
+ *
+ * Inline event handlers (`onclick="..."`) and `hx-on` are still blocked by the default policy,
+ * wire clicks with `yc_click` (see `web_client::server::yc_control`) instead.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContentSecurityPolicy {
+    directives: Vec<(String, String)>,
+    report_only: bool,
+}
+
+impl Default for ContentSecurityPolicy {
+    fn default() -> Self {
+        Self::new()
+            .directive("default-src", "'self'")
+            .directive("script-src", format!("'self' 'nonce-{}'", NONCE))
+            // htmx and transitions style elements inline.
+            .directive("style-src", "'self' 'unsafe-inline'")
+            .directive("img-src", "'self' data: https:")
+            .directive("connect-src", "'self'")
+            .directive("object-src", "'none'")
+            .directive("base-uri", "'self'")
+    }
+}
+
+impl ContentSecurityPolicy {
+    // An empty policy, see `default()` for the one we use.
+    pub fn new() -> Self {
+        Self {
+            directives: vec![],
+            report_only: false,
+        }
+    }
+
+    // Adds a directive, or replaces it if the policy has it already.
+    pub fn directive<N: Into<String>, V: Into<String>>(mut self, name: N, value: V) -> Self {
+        let name = name.into();
+        let value = value.into();
+
+        match self.directives.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, v)) => *v = value,
+            None => self.directives.push((name, value)),
+        }
+
+        self
+    }
+
+    // Violations are reported (in the browser console) instead of blocked.
+    pub fn report_only(mut self) -> Self {
+        self.report_only = true;
+        self
+    }
+
+    pub fn header_value(&self, nonce: &str) -> String {
+        self.directives
+            .iter()
+            .map(|(name, value)| format!("{} {}", name, value.replace(NONCE, nonce)))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+/**
+ * CspNonce
+ *
+ * The nonce of the current request, added to the request extensions by `csp_layer`.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct CspNonce(pub String);
+
+// 128 random bits, hex encoded (a valid CSP base64-value).
+pub fn generate_nonce() -> String {
+    rand::random::<[u8; 16]>()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+pub async fn csp_layer(
+    State(policy): State<ContentSecurityPolicy>,
+    mut request: Request<Body>,
+    next: Next,
+) -> Response {
+    let nonce = generate_nonce();
+    request.extensions_mut().insert(CspNonce(nonce.clone()));
+
+    let mut response = next.run(request).await;
+
+    let header = match policy.report_only {
+        true => CONTENT_SECURITY_POLICY_REPORT_ONLY,
+        false => CONTENT_SECURITY_POLICY,
+    };
+    let value = HeaderValue::from_str(&policy.header_value(&nonce))
+        .expect("Content-Security-Policy should be a valid header value");
+    response.headers_mut().insert(header, value);
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header_value_has_nonce() {
+        let policy = ContentSecurityPolicy::new()
+            .directive("default-src", "'self'")
+            .directive("script-src", format!("'nonce-{}'", NONCE));
+
+        assert_eq!(
+            policy.header_value("abc123"),
+            "default-src 'self'; script-src 'nonce-abc123'"
+        );
+    }
+
+    #[test]
+    fn test_directive_replaces_existing_directive() {
+        let policy = ContentSecurityPolicy::default().directive("img-src", "'self'");
+
+        assert!(policy.header_value("abc123").contains("img-src 'self';"));
+        assert!(!policy.header_value("abc123").contains("data:"));
+    }
+
+    #[test]
+    fn test_nonces_are_unique() {
+        let nonce = generate_nonce();
+
+        assert_eq!(nonce.len(), 32);
+        assert_ne!(nonce, generate_nonce());
+    }
+}
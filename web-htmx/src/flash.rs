@@ -0,0 +1,147 @@
+use axum::{
+    body::Body,
+    extract::State,
+    http::{header::COOKIE, HeaderMap, HeaderValue, Request},
+    middleware::Next,
+    response::Response,
+};
+use axum_extra::extract::cookie::SignedCookieJar;
+use axum_flash::Key;
+
+// The cookie axum_flash keeps its messages in.
+const FLASH_COOKIE: &str = "axum-flash";
+
+/**
+ * FlashKeys
+ *
+ * The keys flash cookies are signed with. New cookies are signed with `current`, cookies signed
+ * with one of the `previous` keys are still accepted (see `flash_keys_layer`), so the key can be
+ * rotated without losing the flashes in flight:
+ *
+ * FlashKeys::new(new_key).previous(vec![old_key])
+ */
+#[derive(Clone)]
+pub struct FlashKeys {
+    current: Key,
+    previous: Vec<Key>,
+}
+
+impl FlashKeys {
+    pub fn new(current: Key) -> Self {
+        Self {
+            current,
+            previous: vec![],
+        }
+    }
+
+    pub fn previous(mut self, previous: Vec<Key>) -> Self {
+        self.previous = previous;
+        self
+    }
+
+    pub fn config(&self) -> axum_flash::Config {
+        axum_flash::Config::new(self.current.clone())
+    }
+}
+
+// axum_flash only knows the current key, re-signs flash cookies signed with a previous key before
+// it reads them.
+pub async fn flash_keys_layer(
+    State(keys): State<FlashKeys>,
+    mut request: Request<Body>,
+    next: Next,
+) -> Response {
+    if !keys.previous.is_empty() {
+        resign_flash_cookie(&keys, request.headers_mut());
+    }
+
+    next.run(request).await
+}
+
+fn resign_flash_cookie(keys: &FlashKeys, headers: &mut HeaderMap) {
+    if SignedCookieJar::from_headers(headers, keys.current.clone())
+        .get(FLASH_COOKIE)
+        .is_some()
+    {
+        return;
+    }
+
+    let Some(cookie) = keys
+        .previous
+        .iter()
+        .find_map(|key| SignedCookieJar::from_headers(headers, key.clone()).get(FLASH_COOKIE))
+    else {
+        return;
+    };
+
+    let mut jar = cookie::CookieJar::new();
+    jar.signed_mut(&keys.current).add(cookie);
+    let Some(signed) = jar.get(FLASH_COOKIE) else {
+        return;
+    };
+
+    let cookies = headers
+        .get_all(COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.split_once('=') {
+            Some((FLASH_COOKIE, _)) => signed.stripped().to_string(),
+            _ => pair.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("; ");
+
+    if let Ok(value) = HeaderValue::from_str(&cookies) {
+        headers.insert(COOKIE, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum_extra::extract::cookie::Cookie;
+
+    fn signed_flash_cookie(key: &Key) -> String {
+        let mut jar = cookie::CookieJar::new();
+        jar.signed_mut(key)
+            .add(Cookie::new(FLASH_COOKIE, r#"[{"level":"Success"}]"#));
+
+        jar.get(FLASH_COOKIE).unwrap().stripped().to_string()
+    }
+
+    fn headers(cookies: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(COOKIE, cookies.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_resigns_cookies_signed_with_previous_key() {
+        let old_key = Key::generate();
+        let keys = FlashKeys::new(Key::generate()).previous(vec![old_key.clone()]);
+        let mut headers = headers(&format!("id=1; {}", signed_flash_cookie(&old_key)));
+
+        resign_flash_cookie(&keys, &mut headers);
+
+        let jar = SignedCookieJar::from_headers(&headers, keys.current.clone());
+        assert_eq!(
+            jar.get(FLASH_COOKIE).unwrap().value(),
+            r#"[{"level":"Success"}]"#
+        );
+        assert!(headers[COOKIE].to_str().unwrap().starts_with("id=1; "));
+    }
+
+    #[test]
+    fn test_ignores_cookies_signed_with_unknown_key() {
+        let keys = FlashKeys::new(Key::generate()).previous(vec![Key::generate()]);
+        let cookies = signed_flash_cookie(&Key::generate());
+        let mut headers = headers(&cookies);
+
+        resign_flash_cookie(&keys, &mut headers);
+
+        assert_eq!(headers[COOKIE], cookies.as_str());
+    }
+}
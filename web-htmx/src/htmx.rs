@@ -0,0 +1,3 @@
+pub mod fragments;
+pub mod request;
+pub mod response;
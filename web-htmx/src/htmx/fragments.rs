@@ -0,0 +1,112 @@
+use axum::response::{Html, IntoResponse, Response};
+
+/**
+ * HxFragments
+ *
+ * Composes a response body from a primary fragment (swapped into the request's target) and any
+ * number of out of band fragments (see `OobSwap`), so one action can update several regions.
+ *
+ * HxFragments::new(html! { <WorkerRow worker=worker /> })
+ *     .oob(html! { <OobSwap id="workers-count">{count}</OobSwap> })
+ */
+#[derive(Clone, Debug, Default)]
+pub struct HxFragments {
+    primary: String,
+    oob: Vec<String>,
+}
+
+impl HxFragments {
+    pub fn new<S: Into<String>>(primary: S) -> Self {
+        Self {
+            primary: primary.into(),
+            oob: vec![],
+        }
+    }
+    // Only out of band fragments, nothing is swapped into the target.
+    pub fn oob_only() -> Self {
+        Self::default()
+    }
+    pub fn oob<S: Into<String>>(mut self, fragment: S) -> Self {
+        self.oob.push(fragment.into());
+        self
+    }
+    pub fn oob_if<S: Into<String>>(self, condition: bool, fragment: S) -> Self {
+        if condition {
+            self.oob(fragment)
+        } else {
+            self
+        }
+    }
+    pub fn render(&self) -> String {
+        let mut body = self.primary.clone();
+        for fragment in &self.oob {
+            body.push_str(fragment);
+        }
+        body
+    }
+}
+
+impl From<HxFragments> for String {
+    fn from(fragments: HxFragments) -> Self {
+        fragments.render()
+    }
+}
+
+impl IntoResponse for HxFragments {
+    fn into_response(self) -> Response {
+        Html(self.render()).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::oob_swap::OobSwap;
+    use rscx::html;
+    use web_client::server::hx::SwapStrategy;
+
+    #[tokio::test]
+    async fn test_renders_primary_and_oob_fragments() {
+        let count = 3;
+        let fragments = HxFragments::new("<tr id=\"worker-3\"><td>Zoe</td></tr>")
+            .oob(html! {
+                <OobSwap id="workers-count" tag="span">{count}</OobSwap>
+            })
+            .oob(html! {
+                <OobSwap strategy=SwapStrategy::BeforeEnd target="#notifications">
+                    <p>Worker added</p>
+                </OobSwap>
+            })
+            .oob_if(false, "<p>skipped</p>");
+
+        assert_eq!(
+            fragments.render(),
+            String::from(concat!(
+                "<tr id=\"worker-3\"><td>Zoe</td></tr>",
+                "<span data-rsx=\"OobSwap\" hx-swap-oob=\"outerHTML\" id=\"workers-count\">3</span>",
+                "<div data-rsx=\"OobSwap\" hx-swap-oob=\"beforeend:#notifications\"><p>Worker added</p></div>",
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_oob_only_response() {
+        let fragments =
+            HxFragments::oob_only().oob(html! { <OobSwap id="badge" tag="span">1</OobSwap> });
+
+        assert_eq!(
+            String::from(fragments.clone()),
+            String::from(
+                "<span data-rsx=\"OobSwap\" hx-swap-oob=\"outerHTML\" id=\"badge\">1</span>"
+            )
+        );
+        assert_eq!(
+            fragments
+                .into_response()
+                .headers()
+                .get("content-type")
+                .unwrap(),
+            "text/html; charset=utf-8"
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{request::Parts, HeaderMap},
+};
+use std::convert::Infallible;
+
+// https://htmx.org/reference/#request_headers
+pub const HX_REQUEST: &str = "HX-Request";
+pub const HX_TARGET: &str = "HX-Target";
+pub const HX_TRIGGER: &str = "HX-Trigger";
+pub const HX_TRIGGER_NAME: &str = "HX-Trigger-Name";
+pub const HX_CURRENT_URL: &str = "HX-Current-URL";
+pub const HX_BOOSTED: &str = "HX-Boosted";
+pub const HX_PROMPT: &str = "HX-Prompt";
+pub const HX_HISTORY_RESTORE_REQUEST: &str = "HX-History-Restore-Request";
+
+/**
+ * HxRequest
+ *
+ * The htmx request headers, as an axum extractor. Also available to components through
+ * `context().htmx`.
+ *
+ * async fn handler(hx: HxRequest) -> Html<String> {
+ *     if hx.trigger_name.as_deref() == Some("search") { ... }
+ * }
+ */
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HxRequest {
+    // `HX-Request`: the request was made by htmx.
+    pub is_htmx: bool,
+    // `HX-Target`: id of the target element.
+    pub target: Option<String>,
+    // `HX-Trigger`: id of the element that triggered the request.
+    pub trigger: Option<String>,
+    // `HX-Trigger-Name`: name of the element that triggered the request.
+    pub trigger_name: Option<String>,
+    pub current_url: Option<String>,
+    pub boosted: bool,
+    // `HX-Prompt`: the user's answer to `hx-prompt`.
+    pub prompt: Option<String>,
+    // htmx lost its history cache and needs the full page.
+    pub history_restore_request: bool,
+}
+
+impl HxRequest {
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        Self {
+            is_htmx: header_bool(headers, HX_REQUEST),
+            target: header_string(headers, HX_TARGET),
+            trigger: header_string(headers, HX_TRIGGER),
+            trigger_name: header_string(headers, HX_TRIGGER_NAME),
+            current_url: header_string(headers, HX_CURRENT_URL),
+            boosted: header_bool(headers, HX_BOOSTED),
+            prompt: header_string(headers, HX_PROMPT),
+            history_restore_request: header_bool(headers, HX_HISTORY_RESTORE_REQUEST),
+        }
+    }
+
+    // A fragment can be rendered instead of the full page.
+    pub fn is_partial(&self) -> bool {
+        self.is_htmx && !self.history_restore_request
+    }
+
+    // Was the request triggered by the element with this id (or name)?
+    pub fn is_triggered_by(&self, id_or_name: &str) -> bool {
+        self.trigger.as_deref() == Some(id_or_name)
+            || self.trigger_name.as_deref() == Some(id_or_name)
+    }
+
+    pub fn is_targeting(&self, id: &str) -> bool {
+        self.target.as_deref() == Some(id.trim_start_matches('#'))
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for HxRequest
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(HxRequest::from_headers(&parts.headers))
+    }
+}
+
+fn header_string(headers: &HeaderMap, name: &str) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| !value.is_empty())
+        .map(String::from)
+}
+
+fn header_bool(headers: &HeaderMap, name: &str) -> bool {
+    header_string(headers, name).is_some_and(|value| value.eq_ignore_ascii_case("true"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request};
+
+    #[tokio::test]
+    async fn test_extracts_htmx_headers() {
+        let request = Request::builder()
+            .uri("/workers")
+            .header("hx-request", "true")
+            .header("hx-target", "workers-table")
+            .header("hx-trigger", "search-input")
+            .header("hx-trigger-name", "search")
+            .header("hx-current-url", "http://localhost:3000/workers?page=2")
+            .header("hx-prompt", "Bob")
+            .body(Body::empty())
+            .unwrap();
+        let (mut parts, _) = request.into_parts();
+
+        let hx = HxRequest::from_request_parts(&mut parts, &())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            hx,
+            HxRequest {
+                is_htmx: true,
+                target: Some("workers-table".into()),
+                trigger: Some("search-input".into()),
+                trigger_name: Some("search".into()),
+                current_url: Some("http://localhost:3000/workers?page=2".into()),
+                boosted: false,
+                prompt: Some("Bob".into()),
+                history_restore_request: false,
+            }
+        );
+        assert!(hx.is_partial());
+        assert!(hx.is_triggered_by("search"));
+        assert!(hx.is_targeting("#workers-table"));
+    }
+
+    #[test]
+    fn test_without_htmx_headers() {
+        let hx = HxRequest::from_headers(&HeaderMap::new());
+
+        assert_eq!(hx, HxRequest::default());
+        assert!(!hx.is_partial());
+    }
+
+    #[test]
+    fn test_history_restore_request_is_not_partial() {
+        let mut headers = HeaderMap::new();
+        headers.insert(HX_REQUEST, "true".parse().unwrap());
+        headers.insert(HX_HISTORY_RESTORE_REQUEST, "true".parse().unwrap());
+
+        let hx = HxRequest::from_headers(&headers);
+
+        assert!(hx.is_htmx);
+        assert!(hx.history_restore_request);
+        assert!(!hx.is_partial());
+    }
+}
@@ -0,0 +1,442 @@
+use axum::{
+    http::{HeaderName, HeaderValue, StatusCode},
+    response::{IntoResponseParts, ResponseParts},
+};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use web_client::server::{
+    hx::{HistoryUrl, Swap, Target},
+    notification::{NotificationRequest, NOTIFICATION_REQUEST_EVENT},
+};
+
+// https://htmx.org/reference/#response_headers
+pub const HX_LOCATION: &str = "HX-Location";
+pub const HX_PUSH_URL: &str = "HX-Push-Url";
+pub const HX_REDIRECT: &str = "HX-Redirect";
+pub const HX_REFRESH: &str = "HX-Refresh";
+pub const HX_REPLACE_URL: &str = "HX-Replace-Url";
+pub const HX_RESWAP: &str = "HX-Reswap";
+pub const HX_RETARGET: &str = "HX-Retarget";
+pub const HX_RESELECT: &str = "HX-Reselect";
+pub const HX_TRIGGER: &str = "HX-Trigger";
+pub const HX_TRIGGER_AFTER_SETTLE: &str = "HX-Trigger-After-Settle";
+pub const HX_TRIGGER_AFTER_SWAP: &str = "HX-Trigger-After-Swap";
+
+/**
+ * HxResponse
+ *
+ * htmx response headers. Implements `IntoResponseParts`, so it can be returned with the rest of
+ * a response:
+ *
+ * (flash.success("Saved!"), HxResponse::new().redirect("/workers").retarget("body"))
+ */
+#[derive(Clone, Debug, Default)]
+pub struct HxResponse {
+    headers: Vec<(&'static str, String)>,
+    triggers: Vec<(&'static str, String, Value)>,
+    notifications: Vec<NotificationRequest>,
+}
+
+impl HxResponse {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn redirect<S: Into<String>>(self, url: S) -> Self {
+        self.set(HX_REDIRECT, url.into())
+    }
+    // Client side navigation to `path`, without a full page reload.
+    pub fn location<S: Into<String>>(self, path: S) -> Self {
+        self.set(HX_LOCATION, path.into())
+    }
+    pub fn location_with(self, location: HxLocation) -> Self {
+        self.set(HX_LOCATION, to_json(&location))
+    }
+    pub fn push_url<U: Into<HistoryUrl>>(self, url: U) -> Self {
+        self.set(HX_PUSH_URL, url.into().to_string())
+    }
+    pub fn replace_url<U: Into<HistoryUrl>>(self, url: U) -> Self {
+        self.set(HX_REPLACE_URL, url.into().to_string())
+    }
+    pub fn refresh(self) -> Self {
+        self.set(HX_REFRESH, "true".into())
+    }
+    pub fn retarget<T: Into<Target>>(self, target: T) -> Self {
+        self.set(HX_RETARGET, target.into().to_string())
+    }
+    pub fn reswap<S: Into<Swap>>(self, swap: S) -> Self {
+        self.set(HX_RESWAP, swap.into().to_string())
+    }
+    pub fn reselect<S: Into<String>>(self, selector: S) -> Self {
+        self.set(HX_RESELECT, selector.into())
+    }
+    // Triggers a client side event as soon as the response is received.
+    pub fn trigger<E: Into<String>>(self, event: E) -> Self {
+        self.add_trigger(HX_TRIGGER, event.into(), Value::Null)
+    }
+    // Triggers a client side event, `detail` is set from the payload.
+    pub fn trigger_with<E: Into<String>, T: Serialize>(self, event: E, payload: &T) -> Self {
+        self.add_trigger(HX_TRIGGER, event.into(), to_value(payload))
+    }
+    pub fn trigger_after_settle<E: Into<String>>(self, event: E) -> Self {
+        self.add_trigger(HX_TRIGGER_AFTER_SETTLE, event.into(), Value::Null)
+    }
+    pub fn trigger_after_settle_with<E: Into<String>, T: Serialize>(
+        self,
+        event: E,
+        payload: &T,
+    ) -> Self {
+        self.add_trigger(HX_TRIGGER_AFTER_SETTLE, event.into(), to_value(payload))
+    }
+    pub fn trigger_after_swap<E: Into<String>>(self, event: E) -> Self {
+        self.add_trigger(HX_TRIGGER_AFTER_SWAP, event.into(), Value::Null)
+    }
+    pub fn trigger_after_swap_with<E: Into<String>, T: Serialize>(
+        self,
+        event: E,
+        payload: &T,
+    ) -> Self {
+        self.add_trigger(HX_TRIGGER_AFTER_SWAP, event.into(), to_value(payload))
+    }
+    // Shows a notification (toast) once the response is received.
+    pub fn notify<N: Into<NotificationRequest>>(mut self, notification: N) -> Self {
+        self.notifications.push(notification.into());
+        self
+    }
+
+    // The headers to send, in the order they were set.
+    pub fn headers(&self) -> Vec<(&'static str, String)> {
+        let mut headers = self.headers.clone();
+
+        let mut all_triggers = self.triggers.clone();
+        match self.notifications.as_slice() {
+            [] => (),
+            [notification] => all_triggers.push((
+                HX_TRIGGER,
+                NOTIFICATION_REQUEST_EVENT.into(),
+                to_value(notification),
+            )),
+            notifications => all_triggers.push((
+                HX_TRIGGER,
+                NOTIFICATION_REQUEST_EVENT.into(),
+                to_value(notifications),
+            )),
+        }
+
+        for header in [HX_TRIGGER, HX_TRIGGER_AFTER_SETTLE, HX_TRIGGER_AFTER_SWAP] {
+            let triggers: Vec<(&String, &Value)> = all_triggers
+                .iter()
+                .filter(|(name, _, _)| *name == header)
+                .map(|(_, event, payload)| (event, payload))
+                .collect();
+
+            if triggers.is_empty() {
+                continue;
+            }
+
+            // Event names only, unless one of the events has a payload.
+            let value = if triggers.iter().all(|(_, payload)| payload.is_null()) {
+                triggers
+                    .iter()
+                    .map(|(event, _)| event.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            } else {
+                let events: Map<String, Value> = triggers
+                    .into_iter()
+                    .map(|(event, payload)| (event.clone(), payload.clone()))
+                    .collect();
+                to_json(&events)
+            };
+
+            headers.push((header, value));
+        }
+
+        headers
+    }
+
+    fn set(mut self, header: &'static str, value: String) -> Self {
+        self.headers.retain(|(name, _)| *name != header);
+        self.headers.push((header, value));
+        self
+    }
+
+    fn add_trigger(mut self, header: &'static str, event: String, payload: Value) -> Self {
+        self.triggers
+            .retain(|(name, existing, _)| !(*name == header && *existing == event));
+        self.triggers.push((header, event, payload));
+        self
+    }
+}
+
+impl IntoResponseParts for HxResponse {
+    type Error = (StatusCode, String);
+
+    fn into_response_parts(self, mut res: ResponseParts) -> Result<ResponseParts, Self::Error> {
+        for (name, value) in self.headers() {
+            let value = HeaderValue::from_str(&value).map_err(|_| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("Invalid value for the {} header: {}", name, value),
+                )
+            })?;
+
+            res.headers_mut()
+                .insert(HeaderName::from_static(header_name(name)), value);
+        }
+
+        Ok(res)
+    }
+}
+
+/**
+ * HxLocation
+ *
+ * Context for `HX-Location`, see https://htmx.org/headers/hx-location/
+ */
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct HxLocation {
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub handler: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub swap: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub select: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub values: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<Value>,
+}
+
+impl HxLocation {
+    pub fn new<S: Into<String>>(path: S) -> Self {
+        Self {
+            path: path.into(),
+            ..Default::default()
+        }
+    }
+    pub fn target<T: Into<Target>>(self, target: T) -> Self {
+        Self {
+            target: Some(target.into().to_string()),
+            ..self
+        }
+    }
+    pub fn swap<S: Into<Swap>>(self, swap: S) -> Self {
+        Self {
+            swap: Some(swap.into().to_string()),
+            ..self
+        }
+    }
+    pub fn select<S: Into<String>>(self, selector: S) -> Self {
+        Self {
+            select: Some(selector.into()),
+            ..self
+        }
+    }
+    pub fn values<T: Serialize>(self, values: &T) -> Self {
+        Self {
+            values: Some(to_value(values)),
+            ..self
+        }
+    }
+    pub fn headers<T: Serialize>(self, headers: &T) -> Self {
+        Self {
+            headers: Some(to_value(headers)),
+            ..self
+        }
+    }
+}
+
+// Header names have to be lowercase for `HeaderName::from_static`.
+fn header_name(name: &'static str) -> &'static str {
+    match name {
+        HX_LOCATION => "hx-location",
+        HX_PUSH_URL => "hx-push-url",
+        HX_REDIRECT => "hx-redirect",
+        HX_REFRESH => "hx-refresh",
+        HX_REPLACE_URL => "hx-replace-url",
+        HX_RESWAP => "hx-reswap",
+        HX_RETARGET => "hx-retarget",
+        HX_RESELECT => "hx-reselect",
+        HX_TRIGGER => "hx-trigger",
+        HX_TRIGGER_AFTER_SETTLE => "hx-trigger-after-settle",
+        HX_TRIGGER_AFTER_SWAP => "hx-trigger-after-swap",
+        _ => unreachable!("Unknown htmx response header: {}", name),
+    }
+}
+
+fn to_value<T: Serialize + ?Sized>(value: &T) -> Value {
+    serde_json::to_value(value).expect("htmx header payloads should serialize to JSON")
+}
+
+// JSON for a header value. Header values have to be visible ASCII, so anything else is escaped.
+fn to_json<T: Serialize + ?Sized>(value: &T) -> String {
+    let json = serde_json::to_string(value).expect("htmx header payloads should serialize to JSON");
+
+    let mut escaped = String::with_capacity(json.len());
+    for c in json.chars() {
+        if c.is_ascii() {
+            escaped.push(c);
+        } else {
+            let mut buf = [0; 2];
+            for unit in c.encode_utf16(&mut buf) {
+                escaped.push_str(&format!("\\u{:04x}", unit));
+            }
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::response::{IntoResponse, Response};
+    use serde_json::json;
+    use web_client::server::hx::SwapStrategy;
+
+    fn header(response: &Response, name: &str) -> Option<String> {
+        response
+            .headers()
+            .get(name)
+            .map(|value| value.to_str().unwrap().to_string())
+    }
+
+    #[test]
+    fn test_redirect_and_retarget() {
+        let response = (
+            StatusCode::OK,
+            HxResponse::new().redirect("/workers").retarget("body"),
+            "",
+        )
+            .into_response();
+
+        assert_eq!(header(&response, "hx-redirect"), Some("/workers".into()));
+        assert_eq!(header(&response, "hx-retarget"), Some("body".into()));
+    }
+
+    #[test]
+    fn test_location_with_context() {
+        let hx = HxResponse::new().location_with(
+            HxLocation::new("/workers")
+                .target("#main")
+                .swap(SwapStrategy::InnerHtml)
+                .values(&json!({ "page": 2 })),
+        );
+
+        assert_eq!(
+            hx.headers(),
+            vec![(
+                HX_LOCATION,
+                r##"{"path":"/workers","target":"#main","swap":"innerHTML","values":{"page":2}}"##
+                    .to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_history_refresh_reswap_and_reselect() {
+        let hx = HxResponse::new()
+            .push_url("/workers?page=2")
+            .replace_url(false)
+            .refresh()
+            .reswap(SwapStrategy::OuterHtml)
+            .reselect("#workers-table");
+
+        assert_eq!(
+            hx.headers(),
+            vec![
+                (HX_PUSH_URL, "/workers?page=2".to_string()),
+                (HX_REPLACE_URL, "false".to_string()),
+                (HX_REFRESH, "true".to_string()),
+                (HX_RESWAP, "outerHTML".to_string()),
+                (HX_RESELECT, "#workers-table".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_triggers_without_payload() {
+        let hx = HxResponse::new()
+            .trigger("workersChanged")
+            .trigger("closeModal")
+            .trigger_after_swap("focusSearch");
+
+        assert_eq!(
+            hx.headers(),
+            vec![
+                (HX_TRIGGER, "workersChanged, closeModal".to_string()),
+                (HX_TRIGGER_AFTER_SWAP, "focusSearch".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_triggers_with_payload() {
+        let hx = HxResponse::new()
+            .trigger("closeModal")
+            .trigger_with("workerAdded", &json!({ "name": "Zoë" }))
+            .trigger_after_settle_with("highlight", &"#row-1");
+        let response = (hx, ()).into_response();
+
+        assert_eq!(
+            header(&response, "hx-trigger"),
+            Some(r#"{"closeModal":null,"workerAdded":{"name":"Zo\u00eb"}}"#.into())
+        );
+        assert_eq!(
+            header(&response, "hx-trigger-after-settle"),
+            Some(r##"{"highlight":"#row-1"}"##.into())
+        );
+    }
+
+    #[test]
+    fn test_notifications() {
+        let trigger_json = |hx: HxResponse| {
+            let (header, value) = hx.headers().pop().unwrap();
+            assert_eq!(header, HX_TRIGGER);
+            serde_json::from_str::<Value>(&value).unwrap()
+        };
+
+        let hx = HxResponse::new()
+            .trigger("closeModal")
+            .notify(NotificationRequest::success("Saved!"));
+
+        assert_eq!(
+            trigger_json(hx),
+            json!({
+                "closeModal": null,
+                "yc:notificationRequest": { "kind": "SUCCESS", "message": "Saved!" },
+            })
+        );
+
+        let hx = HxResponse::new()
+            .notify(NotificationRequest::success("Saved!"))
+            .notify(NotificationRequest::generic(
+                "Heads up",
+                "3 workers changed",
+            ));
+
+        assert_eq!(
+            trigger_json(hx),
+            json!({
+                "yc:notificationRequest": [
+                    { "kind": "SUCCESS", "message": "Saved!" },
+                    { "kind": "GENERIC", "title": "Heads up", "message": "3 workers changed" },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn test_invalid_header_value() {
+        let response = (HxResponse::new().redirect("/new\nline"), "body").into_response();
+
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
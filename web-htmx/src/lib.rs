@@ -4,7 +4,7 @@ use axum::{
     routing::get,
     Router,
 };
-use axum_login::login_required;
+use axum_login::AuthnBackend;
 use http::StatusCode;
 use rscx::html;
 use state::WebHtmxState;
@@ -14,25 +14,53 @@ use web_client::routes as client_routes;
 //##PLOP USE RESOURCE HOOK##
 use components::{not_found_message::NotFoundMessage, page::PageLayout};
 use context::provide_context_layer;
+use csp::csp_layer;
+use flash::flash_keys_layer;
+use resources::{
+    auth::{auth_routes, Credentials},
+    notifications::notifications_routes,
+};
 use routes::{CLIENT, HOME, HOME_REDIRECT, PLAYGROUND};
 
 pub mod components;
 pub mod context;
+pub mod csp;
+pub mod flash;
+pub mod htmx;
 pub mod livereload;
 pub mod playground;
 pub mod resources;
 mod routes;
 pub mod state;
+pub mod trace;
 
-pub fn routes(state: WebHtmxState) -> Router {
+// `B` is the app's auth backend, web-htmx only needs it to sign users in and out.
+pub fn routes<B>(state: WebHtmxState) -> Router
+where
+    B: AuthnBackend<Credentials = Credentials> + Send + Sync + 'static,
+{
     Router::new()
         .with_state(state.clone())
         //##PLOP MERGE ROUTE HOOK##
+        .merge(auth_routes::<B>(state.clone()))
+        .merge(notifications_routes(state.clone()))
         .route(HOME, get(Redirect::temporary(HOME_REDIRECT)))
-        .nest(PLAYGROUND, playground::routes())
+        .nest(PLAYGROUND, playground::routes(state.clone()))
         .nest_service(CLIENT, client_routes())
         .fallback(fallback)
-        .layer(middleware::from_fn_with_state(state, provide_context_layer))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            provide_context_layer,
+        ))
+        // Outside of the context layer, it reads the flashes.
+        .layer(middleware::from_fn_with_state(
+            state.flash_keys.clone(),
+            flash_keys_layer,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.content_security_policy,
+            csp_layer,
+        ))
 }
 
 async fn fallback() -> (StatusCode, Html<String>) {
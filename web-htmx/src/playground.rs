@@ -1,8 +1,9 @@
 use crate::components::page::PageLayout;
+use crate::state::WebHtmxState;
 use axum::{response::Html, routing::get, Router};
 use rscx::{component, html, props};
 
-use auth::AuthPlayground;
+use auth::{auth_routes, AuthPlayground};
 use file_input::{file_input_routes, FileInputPlayground};
 use form::FormPlayground;
 use html_element::HtmlElementPlayground;
@@ -20,14 +21,15 @@ pub mod modal;
 pub mod notifications;
 pub mod page;
 
-pub fn routes() -> Router {
+pub fn routes(state: WebHtmxState) -> Router {
     Router::new()
         .route("/", get(get_playground))
         .nest("/page", page_routes())
         .nest("/htmx", htmx_routes())
         .nest("/modals", modal_routes())
-        .nest("/notifications", notification_routes())
+        .nest("/notifications", notification_routes(state))
         .nest("/file-input", file_input_routes())
+        .nest("/auth", auth_routes())
 }
 
 // ### Route Handlers ###
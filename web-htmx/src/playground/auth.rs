@@ -1,7 +1,35 @@
+use axum::{middleware, response::Html, routing::get, Router};
 use rscx::{component, html, props};
 
 use web_client::server::button::PrimaryButton;
 
+use crate::{
+    components::{login_form::LogoutForm, page::PageLayout},
+    context::CurrentUser,
+    resources::auth::require_login,
+};
+
+pub fn auth_routes() -> Router {
+    Router::new()
+        .route("/authenticated", get(get_authenticated))
+        .layer(middleware::from_fn(require_login))
+}
+
+// ### Route Handlers ###
+
+async fn get_authenticated(current_user: CurrentUser) -> Html<String> {
+    Html(html! {
+        <PageLayout header="Authenticated Page">
+            <section class="py-8">
+                <p class="pb-4">{format!("You're signed in as user {}.", current_user.id)}</p>
+                <LogoutForm />
+            </section>
+        </PageLayout>
+    })
+}
+
+// ### Components ###
+
 #[component]
 pub fn AuthPlayground() -> String {
     html! {
@@ -10,7 +38,7 @@ pub fn AuthPlayground() -> String {
           <div class="flex gap-2">
               <PrimaryButton
                   tag="a"
-                  href="/wallchart"
+                  href="/playground/auth/authenticated"
               >
                   Authenticated page link
               </PrimaryButton>
@@ -17,8 +17,7 @@ pub fn file_input_routes() -> Router {
 // ### Route Handlers ###
 
 async fn upload_file(mut multipart: Multipart) -> impl IntoResponse {
-    println!("Uploading file!");
-    println!("Multipart: {:?}", multipart);
+    tracing::debug!(?multipart, "Uploading file!");
 
     let mut content: String = String::new();
 
@@ -1,7 +1,7 @@
 #![allow(unused_braces)]
 use rscx::{component, html, props};
 
-use web_client::server::html_element::HtmlElement;
+use web_client::server::{html_element::HtmlElement, yc_control::yc_click};
 use web_macros::*;
 
 #[component]
@@ -51,7 +51,7 @@ fn MessageButton(props: MessageButtonProps) -> String {
             tag="button"
             id="btn-alert"
             class="bg-slate-200 ml-4 p-3 rounded-full"
-            onclick={format!("alert('{}')", props.message)}
+            attrs=yc_click("showInfoNotification", &[&props.message])
         >
             {props.children}
         </HtmlElement>
@@ -59,6 +59,7 @@ fn MessageButton(props: MessageButtonProps) -> String {
 }
 
 // This macro adds all standard HTML attributes for your component!
+// Pick attribute groups and extra attributes instead with: #[html_element(global, aria, link, "data-testid")]
 #[html_element]
 pub struct SimpleElementProps {
     #[builder(default)]
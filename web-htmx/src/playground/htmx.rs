@@ -1,7 +1,16 @@
-use axum::{response::Html, routing::get, Router};
+use axum::{routing::get, Router};
 use rscx::{component, html, props};
 
-use web_client::server::button::SecondaryButton;
+use web_client::server::{
+    button::SecondaryButton,
+    escape::escape_text,
+    hx::{Hx, SwapStrategy},
+};
+
+use crate::{
+    components::oob_swap::OobSwap,
+    htmx::{fragments::HxFragments, request::HxRequest},
+};
 
 pub fn htmx_routes() -> Router {
     Router::new().route("/", get(htmx_test))
@@ -9,8 +18,20 @@ pub fn htmx_routes() -> Router {
 
 // ### Route Handlers ###
 
-async fn htmx_test() -> Html<String> {
-    Html("Is this the real life? Is this just fantasy?".into())
+async fn htmx_test(hx: HxRequest) -> HxFragments {
+    let primary = match &hx.trigger {
+        Some(trigger) => format!(
+            "Is this the real life? Is this just fantasy? (triggered by #{})",
+            escape_text(trigger)
+        ),
+        None => "Is this the real life? Is this just fantasy?".into(),
+    };
+
+    HxFragments::new(primary).oob(html! {
+        <OobSwap id="htmx-playground-status" tag="p" class="text-sm text-gray-500">
+            Updated out of band!
+        </OobSwap>
+    })
 }
 
 // ### Components ###
@@ -21,13 +42,11 @@ pub fn HtmxPlayground() -> String {
         <section class="py-8">
             <h2 class="text-xl font-bold">HTMX Rendering</h2>
             <div class="flex gap-2">
-                <SecondaryButton
-                    hx_get="/playground/htmx"
-                    hx_swap="outerHTML"
-                >
+                <SecondaryButton id="htmx-playground-button" hx=Hx::get("/playground/htmx").swap(SwapStrategy::OuterHtml)>
                     Click me!
                 </SecondaryButton>
             </div>
+            <p id="htmx-playground-status" class="text-sm text-gray-500">Not clicked yet.</p>
         </section>
     }
 }
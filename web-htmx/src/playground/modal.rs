@@ -6,8 +6,10 @@ use axum::{
 };
 use rscx::{component, html, props};
 use web_client::server::{
+    attrs::Attrs,
     button::PrimaryButton,
     flyout::Flyout,
+    hx::Hx,
     modal::Modal,
     notification::{NotificationCall, NotificationPresenter},
 };
@@ -54,16 +56,10 @@ pub fn ModalPlayground() -> String {
                 <section>
                     <p><em>Open models and flyouts for fun AND non-profit.</em></p>
                     <div class="flex gap-2">
-                        <PrimaryButton
-                            hx_get="/playground/modals/modal-one"
-                            hx_target="#modals-root"
-                        >
+                        <PrimaryButton hx=Hx::get("/playground/modals/modal-one").target("#modals-root")>
                             Open Simple Modal
                         </PrimaryButton>
-                        <PrimaryButton
-                            hx_get="/playground/modals/flyout-one"
-                            hx_target="#modals-root"
-                        >
+                        <PrimaryButton hx=Hx::get("/playground/modals/flyout-one").target("#modals-root")>
                             Open Flyout
                         </PrimaryButton>
                     </div>
@@ -72,11 +68,13 @@ pub fn ModalPlayground() -> String {
                     <p><em>Special client-side only modals</em></p>
                     <div class="flex gap-2">
                         <PrimaryButton
-                            onclick="YcControls.confirmDelete({
-                                title: 'Delete Foo',
-                                message: 'Are you sure you want to delete Foo?',
-                                deleteHref: '/playground/modals/foo',
-                            })" 
+                            hx=Hx::delete("/playground/modals/foo")
+                                .confirm("Delete Foo")
+                                .target("#modals-root")
+                            attrs=Attrs::with(
+                                "data-confirm-message",
+                                "Are you sure you want to delete Foo?".into(),
+                            )
                         >
                             Confirm Delete of Foo
                         </PrimaryButton>
@@ -1,24 +1,41 @@
 use axum::{
+    extract::State,
+    http::StatusCode,
     response::Html,
     routing::{get, post},
     Router,
 };
+use notifications_service::publish_notification::PublishNotificationInput;
 use rscx::{component, html, props};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use web_client::server::{
     attrs::Attrs,
     button::{PrimaryButton, SecondaryButton},
+    hx::{Hx, SwapStrategy},
     notification::{
-        NoticationCloseButton, NotificationCall, NotificationPresenter, NotificationTransition,
+        NoticationCloseButton, NotificationAction, NotificationCall, NotificationOptions,
+        NotificationPresenter, NotificationRequest, NotificationTransition,
     },
+    yc_control::yc_click,
 };
 
-pub fn notification_routes() -> Router {
+use crate::{
+    components::notification_center::NOTIFICATIONS_CHANGED_EVENT, context::CurrentUser,
+    htmx::response::HxResponse, state::WebHtmxState,
+};
+
+pub fn notification_routes(state: WebHtmxState) -> Router {
     Router::new()
         .route("/business-logic-example", post(post_business_logic))
+        .route("/hx-trigger-example", post(post_hx_trigger_example))
+        .route("/undo-example", post(post_undo_example))
+        .route("/undo-example/restore", post(post_undo_example_restore))
+        .route("/sticky-example", post(post_sticky_example))
+        .route("/publish-example", post(post_publish_example))
         .route("/custom-1", get(get_custom_notification1))
         .route("/custom-2", get(get_custom_notification2))
+        .with_state(state)
 }
 
 // ### Route Handlers ###
@@ -71,6 +88,84 @@ async fn post_business_logic() -> Html<String> {
     })
 }
 
+async fn post_hx_trigger_example() -> (HxResponse, Html<String>) {
+    // Notifications sent in the HX-Trigger header don't depend on what gets swapped.
+    (
+        HxResponse::new()
+            .notify(NotificationRequest::success(
+                "Sent with the HX-Trigger header!",
+            ))
+            .notify(NotificationRequest::generic(
+                "No inline scripts",
+                "This one came along in the same response.",
+            )),
+        Html("Action complete.".into()),
+    )
+}
+
+async fn post_undo_example() -> (HxResponse, Html<String>) {
+    // Pretend something was deleted, and offer to bring it back.
+    (
+        HxResponse::new().notify(
+            NotificationRequest::success("Foo was deleted.")
+                .action(NotificationAction::new(
+                    "Undo",
+                    Hx::post("/playground/notifications/undo-example/restore"),
+                ))
+                .timeout(Duration::from_secs(10)),
+        ),
+        Html("Foo deleted.".into()),
+    )
+}
+
+async fn post_undo_example_restore() -> (HxResponse, Html<String>) {
+    (
+        HxResponse::new().notify(NotificationRequest::info("Foo is back!")),
+        Html("Foo restored.".into()),
+    )
+}
+
+async fn post_sticky_example() -> Html<String> {
+    Html(html! {
+        "Action complete."
+        <NotificationPresenter
+            call=NotificationCall::Warning("This stays until you close it.".into())
+            options=NotificationOptions::new().sticky()
+        />
+    })
+}
+
+async fn post_publish_example(
+    State(state): State<WebHtmxState>,
+    current_user: Option<CurrentUser>,
+) -> Result<(HxResponse, Html<String>), StatusCode> {
+    let Some(user) = current_user else {
+        return Ok((
+            HxResponse::new().notify(NotificationRequest::warning(
+                "Sign in to get notifications.",
+            )),
+            Html("Nobody to notify.".into()),
+        ));
+    };
+
+    state
+        .notifications_service
+        .publish_notification(PublishNotificationInput {
+            user_id: user.id,
+            title: "Hello from the playground".into(),
+            message: "This notification is waiting for you behind the bell.".into(),
+            url: None,
+        })
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok((
+        // Tells the bell to update its badge.
+        HxResponse::new().trigger(NOTIFICATIONS_CHANGED_EVENT),
+        Html("Notification published.".into()),
+    ))
+}
+
 // ### Components ###
 
 #[component]
@@ -82,18 +177,20 @@ pub fn NotificationsPlayground() -> String {
                 <section>
                     <p><em>Show a toast notification (client-side).</em></p>
                     <div class="flex gap-2">
-                        <PrimaryButton
-                            onclick="YcControls.showSuccessNotification('Success feels so good!')"
-                        >
+                        <PrimaryButton attrs=yc_click("showSuccessNotification", &["Success feels so good!"])>
                             Show Success
                         </PrimaryButton>
-                        <PrimaryButton
-                            onclick="YcControls.showErrorNotification('This is an error notification.')"
-                        >
+                        <PrimaryButton attrs=yc_click("showErrorNotification", &["This is an error notification."])>
                             Show Error
                         </PrimaryButton>
+                        <PrimaryButton attrs=yc_click("showWarningNotification", &["This is a warning notification."])>
+                            Show Warning
+                        </PrimaryButton>
+                        <PrimaryButton attrs=yc_click("showInfoNotification", &["This is an info notification."])>
+                            Show Info
+                        </PrimaryButton>
                         <PrimaryButton
-                            onclick="YcControls.showNotification('This just in', 'You are still not done!')"
+                            attrs=yc_click("showNotification", &["This just in", "You are still not done!"])
                         >
                             Show Generic
                         </PrimaryButton>
@@ -102,27 +199,51 @@ pub fn NotificationsPlayground() -> String {
                 <section>
                     <p><em>Show a toast notification (server-side).</em></p>
                     <div class="flex gap-2">
+                        <PrimaryButton hx=Hx::post("/playground/notifications/business-logic-example")>
+                            Show Success
+                        </PrimaryButton>
                         <PrimaryButton
-                            hx_post="/playground/notifications/business-logic-example"
+                            hx=Hx::post("/playground/notifications/hx-trigger-example")
+                                .swap(SwapStrategy::None)
                         >
-                            Show Success
+                            Show Success (HX-Trigger)
+                        </PrimaryButton>
+                        <PrimaryButton
+                            hx=Hx::post("/playground/notifications/undo-example")
+                                .swap(SwapStrategy::None)
+                        >
+                            Delete w/ Undo
+                        </PrimaryButton>
+                        <PrimaryButton hx=Hx::post("/playground/notifications/sticky-example")>
+                            Show Sticky
                         </PrimaryButton>
                         <PrimaryButton
-                            hx_get="/playground/notifications/custom-1"
-                            hx_target="body"
-                            hx_swap="beforeend"
+                            hx=Hx::get("/playground/notifications/custom-1")
+                                .target("body")
+                                .swap(SwapStrategy::BeforeEnd)
                         >
                             Show Custom
                         </PrimaryButton>
                         <PrimaryButton
-                        hx_get="/playground/notifications/custom-2"
-                        hx_target="body"
-                            hx_swap="beforeend"
+                            hx=Hx::get("/playground/notifications/custom-2")
+                                .target("body")
+                                .swap(SwapStrategy::BeforeEnd)
                         >
                             Show Custom w/ Standard Components
                         </PrimaryButton>
                     </div>
                 </section>
+                <section>
+                    <p><em>Publish to the notification center (behind the bell).</em></p>
+                    <div class="flex gap-2">
+                        <PrimaryButton
+                            hx=Hx::post("/playground/notifications/publish-example")
+                                .swap(SwapStrategy::None)
+                        >
+                            Publish Notification
+                        </PrimaryButton>
+                    </div>
+                </section>
             </div>
         </section>
     }
@@ -0,0 +1,431 @@
+use axum::{
+    body::Body,
+    extract::{OriginalUri, Query},
+    http::{Request, StatusCode},
+    middleware::Next,
+    response::{Html, IntoResponse, Redirect, Response},
+    routing::get,
+    Form, Router,
+};
+use axum_flash::Flash;
+use axum_login::{tower_sessions::Session, AuthSession, AuthnBackend};
+use rscx::html;
+use serde::Deserialize;
+
+use crate::{
+    components::{
+        login_form::{LoginForm, LogoutForm},
+        page::PageLayout,
+    },
+    context::CurrentUser,
+    routes,
+    state::WebHtmxState,
+};
+
+/**
+ * Credentials
+ *
+ * The login form. The app's auth backend checks `username` and `password`, `next` is where to
+ * send the user once they're signed in.
+ */
+#[derive(Clone, Deserialize)]
+pub struct Credentials {
+    pub username: String,
+    pub password: String,
+    pub next: Option<String>,
+}
+
+// Keep passwords out of the logs.
+impl std::fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Credentials")
+            .field("username", &self.username)
+            .field("password", &"[redacted]")
+            .field("next", &self.next)
+            .finish()
+    }
+}
+
+#[derive(Deserialize)]
+struct LoginQuery {
+    next: Option<String>,
+}
+
+pub fn auth_routes<B>(state: WebHtmxState) -> Router
+where
+    B: AuthnBackend<Credentials = Credentials> + Send + Sync + 'static,
+{
+    Router::new()
+        .route(routes::LOGIN, get(get_login).post(post_login::<B>))
+        .route(routes::LOGOUT, get(get_logout).post(post_logout::<B>))
+        .with_state(state)
+}
+
+async fn get_login(Query(LoginQuery { next }): Query<LoginQuery>) -> Html<String> {
+    Html(html! {
+        <PageLayout header="Sign in">
+            <LoginForm next=next.unwrap_or_default() />
+        </PageLayout>
+    })
+}
+
+async fn post_login<B>(
+    mut auth_session: AuthSession<B>,
+    session: Session,
+    flash: Flash,
+    Form(credentials): Form<Credentials>,
+) -> Result<Response, StatusCode>
+where
+    B: AuthnBackend<Credentials = Credentials> + Send + Sync + 'static,
+{
+    let next = credentials.next.clone();
+
+    let user = match auth_session.authenticate(credentials).await {
+        Ok(Some(user)) => user,
+        Ok(None) => {
+            let login = match &next {
+                Some(next) if !next.is_empty() => routes::login_with_next(next),
+                _ => routes::login(),
+            };
+
+            return Ok((
+                flash.error("Invalid username or password."),
+                Redirect::to(&login),
+            )
+                .into_response());
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "failed to authenticate");
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    auth_session.login(&user).await.map_err(|e| {
+        tracing::error!(error = %e, "failed to log in");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    // Session fixation: the signed in session never keeps the id it had before. axum-login only
+    // cycles it when no one was signed in yet.
+    session.cycle_id();
+
+    Ok((
+        flash.success("Signed in."),
+        Redirect::to(&local_redirect(next)),
+    )
+        .into_response())
+}
+
+async fn get_logout() -> Html<String> {
+    Html(html! {
+        <PageLayout header="Sign out">
+            <LogoutForm />
+        </PageLayout>
+    })
+}
+
+async fn post_logout<B>(
+    mut auth_session: AuthSession<B>,
+    flash: Flash,
+) -> Result<impl IntoResponse, StatusCode>
+where
+    B: AuthnBackend<Credentials = Credentials> + Send + Sync + 'static,
+{
+    auth_session.logout().map_err(|e| {
+        tracing::error!(error = %e, "failed to log out");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok((flash.info("Signed out."), Redirect::to(&routes::login())))
+}
+
+/**
+ * require_login
+ *
+ * Sends visitors who aren't signed in to the login page, and back once they've signed in.
+ *
+ * Router::new()
+ *     .route("/secret", get(get_secret))
+ *     .layer(middleware::from_fn(require_login))
+ */
+pub async fn require_login(
+    current_user: Option<CurrentUser>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    if current_user.is_some() {
+        return next.run(request).await;
+    }
+
+    // Nested routers only see the rest of the path.
+    let uri = request
+        .extensions()
+        .get::<OriginalUri>()
+        .map(|OriginalUri(uri)| uri)
+        .unwrap_or(request.uri());
+    let page = uri
+        .path_and_query()
+        .map(|path| path.to_string())
+        .unwrap_or_else(|| uri.path().to_string());
+
+    Redirect::to(&routes::login_with_next(&page)).into_response()
+}
+
+// Only redirects within the site, `next` comes from the request. Browsers skip whitespace and
+// control characters (`/\t/evil.com` is `//evil.com`), and they can't go in a header anyway.
+fn local_redirect(next: Option<String>) -> String {
+    match next {
+        Some(next)
+            if next.starts_with('/')
+                && !next.starts_with("//")
+                && !next.contains('\\')
+                && !next.chars().any(|c| c.is_whitespace() || c.is_control()) =>
+        {
+            next
+        }
+        _ => routes::home(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{convert::Infallible, sync::Arc};
+
+    use axum::{
+        async_trait,
+        error_handling::HandleErrorLayer,
+        http::{
+            header::{CONTENT_TYPE, COOKIE, LOCATION, SET_COOKIE},
+            HeaderMap,
+        },
+        middleware, BoxError,
+    };
+    use axum_extra::extract::cookie::SignedCookieJar;
+    use axum_flash::Key;
+    use axum_login::{
+        tower_sessions::{MemoryStore, SessionManagerLayer},
+        AuthManagerLayerBuilder, AuthUser, UserId,
+    };
+    use in_memory_notification_repository::InMemoryNotificationRepository;
+    use notifications_service::service::NotificationsService;
+    use tower::{ServiceBuilder, ServiceExt};
+
+    use crate::{csp::ContentSecurityPolicy, flash::FlashKeys};
+
+    #[derive(Clone, Debug)]
+    struct TestUser;
+
+    impl AuthUser for TestUser {
+        type Id = String;
+
+        fn id(&self) -> Self::Id {
+            "ann".into()
+        }
+
+        fn session_auth_hash(&self) -> &[u8] {
+            b"correct horse"
+        }
+    }
+
+    // Knows one user: ann, "correct horse".
+    #[derive(Clone)]
+    struct TestBackend;
+
+    #[async_trait]
+    impl AuthnBackend for TestBackend {
+        type User = TestUser;
+        type Credentials = Credentials;
+        type Error = Infallible;
+
+        async fn authenticate(
+            &self,
+            credentials: Self::Credentials,
+        ) -> Result<Option<Self::User>, Self::Error> {
+            Ok(
+                (credentials.username == "ann" && credentials.password == "correct horse")
+                    .then_some(TestUser),
+            )
+        }
+
+        async fn get_user(&self, id: &UserId<Self>) -> Result<Option<Self::User>, Self::Error> {
+            Ok((id == "ann").then_some(TestUser))
+        }
+    }
+
+    fn app(flash_key: Key) -> Router {
+        let state = WebHtmxState {
+            flash_keys: FlashKeys::new(flash_key),
+            notifications_service: NotificationsService::new(Arc::new(
+                InMemoryNotificationRepository::empty(),
+            )),
+            content_security_policy: ContentSecurityPolicy::default(),
+        };
+        let auth_layer = ServiceBuilder::new()
+            .layer(HandleErrorLayer::new(|_: BoxError| async {
+                StatusCode::BAD_REQUEST
+            }))
+            .layer(
+                AuthManagerLayerBuilder::new(
+                    TestBackend,
+                    SessionManagerLayer::new(MemoryStore::default()),
+                )
+                .build(),
+            );
+
+        Router::new()
+            .route(
+                "/visit",
+                get(|session: Session| async move { session.insert("visited", true).unwrap() }),
+            )
+            .route(
+                "/secret",
+                get(|| async { "secret" }).layer(middleware::from_fn(require_login)),
+            )
+            .merge(auth_routes::<TestBackend>(state))
+            .layer(auth_layer)
+    }
+
+    fn login_request(body: &str, session_cookie: &str) -> Request<Body> {
+        Request::post(routes::LOGIN)
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .header(COOKIE, session_cookie)
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    // `name=value` of the cookie the response sets.
+    fn set_cookie(headers: &HeaderMap, name: &str) -> Option<String> {
+        headers
+            .get_all(SET_COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .filter_map(|value| value.split(';').next())
+            .find(|pair| pair.starts_with(&format!("{}=", name)))
+            .map(str::to_string)
+    }
+
+    fn flash_messages(headers: &HeaderMap, key: Key) -> String {
+        let mut cookies = HeaderMap::new();
+        cookies.insert(
+            COOKIE,
+            set_cookie(headers, "axum-flash").unwrap().parse().unwrap(),
+        );
+
+        SignedCookieJar::from_headers(&cookies, key)
+            .get("axum-flash")
+            .unwrap()
+            .value()
+            .to_string()
+    }
+
+    async fn visit(app: Router) -> String {
+        let response = app
+            .oneshot(Request::get("/visit").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        set_cookie(response.headers(), "id").unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_login_changes_the_session_id() {
+        let key = Key::generate();
+        let app = app(key.clone());
+        let session_cookie = visit(app.clone()).await;
+
+        let response = app
+            .oneshot(login_request(
+                "username=ann&password=correct+horse&next=%2Fsecret",
+                &session_cookie,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.headers()[LOCATION], "/secret");
+        let signed_in_cookie = set_cookie(response.headers(), "id").unwrap();
+        assert_ne!(signed_in_cookie, session_cookie);
+        assert!(flash_messages(response.headers(), key).contains("Signed in."));
+    }
+
+    #[tokio::test]
+    async fn test_login_ignores_next_with_control_characters() {
+        let app = app(Key::generate());
+        let session_cookie = visit(app.clone()).await;
+
+        let response = app
+            .oneshot(login_request(
+                "username=ann&password=correct+horse&next=%2F%0A",
+                &session_cookie,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.headers()[LOCATION], "/");
+    }
+
+    #[tokio::test]
+    async fn test_login_with_bad_credentials_redirects_back_with_an_error() {
+        let key = Key::generate();
+        let app = app(key.clone());
+        let session_cookie = visit(app.clone()).await;
+
+        let response = app
+            .oneshot(login_request(
+                "username=ann&password=battery+staple&next=%2Fsecret",
+                &session_cookie,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            response.headers()[LOCATION],
+            routes::login_with_next("/secret").as_str()
+        );
+        let messages = flash_messages(response.headers(), key);
+        assert!(messages.contains("Error"));
+        assert!(messages.contains("Invalid username or password."));
+    }
+
+    #[tokio::test]
+    async fn test_require_login_redirects_anonymous_visitors() {
+        let response = app(Key::generate())
+            .oneshot(
+                Request::get("/secret?tab=auth")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        assert_eq!(
+            response.headers()[LOCATION],
+            routes::login_with_next("/secret?tab=auth").as_str()
+        );
+    }
+
+    #[test]
+    fn test_local_redirect() {
+        assert_eq!(local_redirect(Some("/playground".into())), "/playground");
+        assert_eq!(local_redirect(None), "/");
+        assert_eq!(local_redirect(Some("".into())), "/");
+        assert_eq!(local_redirect(Some("https://example.com".into())), "/");
+        assert_eq!(local_redirect(Some("//example.com".into())), "/");
+        assert_eq!(local_redirect(Some("/\\example.com".into())), "/");
+        assert_eq!(local_redirect(Some("/\n".into())), "/");
+        assert_eq!(local_redirect(Some("/\t/example.com".into())), "/");
+        assert_eq!(local_redirect(Some("/ /example.com".into())), "/");
+    }
+
+    #[test]
+    fn test_credentials_debug_hides_password() {
+        let credentials = Credentials {
+            username: "ann".into(),
+            password: "hunter2".into(),
+            next: None,
+        };
+
+        assert!(!format!("{:?}", credentials).contains("hunter2"));
+    }
+}
@@ -0,0 +1,128 @@
+use axum::{
+    extract::{Path, State},
+    response::{Html, IntoResponse},
+    routing::{get, post},
+    Router,
+};
+use http::StatusCode;
+use rscx::html;
+
+use notifications_service::{
+    count_unread_notifications::CountUnreadNotificationsInput,
+    get_notifications::GetNotificationsInput,
+    mark_all_notifications_read::MarkAllNotificationsReadInput,
+    mark_notification_read::{MarkNotificationReadFailure, MarkNotificationReadInput},
+    models::Notification,
+};
+
+use crate::{
+    components::notification_center::{
+        NotificationBellButton, NotificationCenterFlyout, NotificationList,
+        NOTIFICATIONS_CHANGED_EVENT,
+    },
+    context::CurrentUser,
+    htmx::response::HxResponse,
+    routes,
+    state::WebHtmxState,
+};
+
+pub fn notifications_routes(state: WebHtmxState) -> Router {
+    Router::new()
+        .route(routes::NOTIFICATIONS, get(get_notifications))
+        .route(routes::NOTIFICATIONS_BELL, get(get_notifications_bell))
+        .route(routes::NOTIFICATIONS_READ_ALL, post(post_read_all))
+        .route(routes::NOTIFICATION_READ, post(post_read))
+        .with_state(state)
+}
+
+async fn get_notifications_bell(
+    State(state): State<WebHtmxState>,
+    current_user: Option<CurrentUser>,
+) -> Result<Html<String>, StatusCode> {
+    let unread_count = match current_user {
+        Some(user) => state
+            .notifications_service
+            .count_unread_notifications(CountUnreadNotificationsInput { user_id: user.id })
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
+        None => 0,
+    };
+
+    Ok(Html(html! {
+        <NotificationBellButton unread_count=unread_count />
+    }))
+}
+
+async fn get_notifications(
+    State(state): State<WebHtmxState>,
+    current_user: CurrentUser,
+) -> Result<Html<String>, StatusCode> {
+    let notifications = user_notifications(&state, current_user).await?;
+
+    Ok(Html(html! {
+        <NotificationCenterFlyout notifications=notifications />
+    }))
+}
+
+async fn post_read(
+    State(state): State<WebHtmxState>,
+    current_user: CurrentUser,
+    Path(notification_id): Path<String>,
+) -> Result<impl IntoResponse, StatusCode> {
+    state
+        .notifications_service
+        .mark_notification_read(MarkNotificationReadInput {
+            user_id: current_user.id.clone(),
+            notification_id,
+        })
+        .await
+        .map_err(|e| match e {
+            MarkNotificationReadFailure::NotFound => StatusCode::NOT_FOUND,
+            MarkNotificationReadFailure::Unknown(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        })?;
+
+    notification_list_response(&state, current_user).await
+}
+
+async fn post_read_all(
+    State(state): State<WebHtmxState>,
+    current_user: CurrentUser,
+) -> Result<impl IntoResponse, StatusCode> {
+    state
+        .notifications_service
+        .mark_all_notifications_read(MarkAllNotificationsReadInput {
+            user_id: current_user.id.clone(),
+        })
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    notification_list_response(&state, current_user).await
+}
+
+// The updated list, and a nudge for the bell to update its badge.
+async fn notification_list_response(
+    state: &WebHtmxState,
+    current_user: CurrentUser,
+) -> Result<(HxResponse, Html<String>), StatusCode> {
+    let notifications = user_notifications(state, current_user).await?;
+
+    Ok((
+        HxResponse::new().trigger(NOTIFICATIONS_CHANGED_EVENT),
+        Html(html! {
+            <NotificationList notifications=notifications />
+        }),
+    ))
+}
+
+async fn user_notifications(
+    state: &WebHtmxState,
+    current_user: CurrentUser,
+) -> Result<Vec<Notification>, StatusCode> {
+    state
+        .notifications_service
+        .get_notifications(GetNotificationsInput {
+            user_id: current_user.id,
+        })
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
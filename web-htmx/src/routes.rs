@@ -37,12 +37,35 @@ pub const LOGIN: &str = "/login";
 pub fn login() -> String {
     LOGIN.into()
 }
+pub fn login_with_next(next: &str) -> String {
+    format!("{}?next={}", LOGIN, urlencoding::encode(next))
+}
 
 pub const LOGOUT: &str = "/logout";
 pub fn logout() -> String {
     LOGOUT.into()
 }
 
+pub const NOTIFICATIONS: &str = "/notifications";
+pub fn notifications() -> String {
+    NOTIFICATIONS.into()
+}
+
+pub const NOTIFICATIONS_BELL: &str = "/notifications/bell";
+pub fn notifications_bell() -> String {
+    NOTIFICATIONS_BELL.into()
+}
+
+pub const NOTIFICATIONS_READ_ALL: &str = "/notifications/read-all";
+pub fn notifications_read_all() -> String {
+    NOTIFICATIONS_READ_ALL.into()
+}
+
+pub const NOTIFICATION_READ: &str = "/notifications/:notification_id/read";
+pub fn notification_read(notification_id: &String) -> String {
+    format!("/notifications/{}/read", notification_id)
+}
+
 pub const WALLCHART: &str = "/wallchart";
 pub fn wallchart() -> String {
     WALLCHART.into()
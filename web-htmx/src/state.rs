@@ -1,12 +1,17 @@
 use axum::extract::FromRef;
+use notifications_service::service::NotificationsService;
+
+use crate::{csp::ContentSecurityPolicy, flash::FlashKeys};
 
 #[derive(Clone)]
 pub struct WebHtmxState {
-    pub flash_config: axum_flash::Config,
+    pub flash_keys: FlashKeys,
+    pub notifications_service: NotificationsService,
+    pub content_security_policy: ContentSecurityPolicy,
 }
 
 impl FromRef<WebHtmxState> for axum_flash::Config {
     fn from_ref(state: &WebHtmxState) -> axum_flash::Config {
-        state.flash_config.clone()
+        state.flash_keys.config()
     }
 }
@@ -0,0 +1,96 @@
+use axum::{
+    body::Body,
+    extract::MatchedPath,
+    http::{HeaderName, HeaderValue, Request},
+    middleware::Next,
+    response::Response,
+};
+use std::time::Instant;
+use tracing::{field::Empty, Instrument};
+
+use crate::htmx::request::HxRequest;
+
+pub const X_REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
+
+/**
+ * request_span_layer
+ *
+ * Runs each request in a `request` span and logs it once it's done:
+ *
+ * request{method=GET route=/notifications/:id/read request_id=... hx.partial=true hx.boosted=false
+ *         hx.history_restore=false status=200 latency_ms=3}: finished request
+ *
+ * The request id comes from `X-Request-Id` when the request has one (ex: set by a proxy), it's
+ * sent back with the response.
+ */
+pub async fn request_span_layer(request: Request<Body>, next: Next) -> Response {
+    let started_at = Instant::now();
+    let request_id = request_id(&request);
+    let hx = HxRequest::from_headers(request.headers());
+    // Templates rather than paths, so ids don't turn each url into its own route.
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "<unmatched>".into());
+
+    let span = tracing::info_span!(
+        "request",
+        method = %request.method(),
+        route,
+        request_id,
+        hx.partial = hx.is_partial(),
+        hx.boosted = hx.boosted,
+        hx.history_restore = hx.history_restore_request,
+        status = Empty,
+        latency_ms = Empty,
+    );
+
+    let mut response = next.run(request).instrument(span.clone()).await;
+
+    let status = response.status();
+    span.record("status", status.as_u16());
+    span.record("latency_ms", started_at.elapsed().as_millis() as u64);
+    span.in_scope(|| match status.is_server_error() {
+        true => tracing::error!("finished request"),
+        false => tracing::info!("finished request"),
+    });
+
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(X_REQUEST_ID, value);
+    }
+
+    response
+}
+
+fn request_id(request: &Request<Body>) -> String {
+    request
+        .headers()
+        .get(X_REQUEST_ID)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| !value.is_empty())
+        .map(String::from)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_id_from_header() {
+        let request = Request::builder()
+            .header(X_REQUEST_ID, "abc-123")
+            .body(Body::empty())
+            .unwrap();
+
+        assert_eq!(request_id(&request), "abc-123");
+    }
+
+    #[test]
+    fn test_request_id_is_generated() {
+        let request = Request::builder().body(Body::empty()).unwrap();
+
+        assert_eq!(request_id(&request).len(), 36);
+    }
+}
@@ -2,12 +2,12 @@ use proc_macro::TokenStream;
 use proc_macro2::Span;
 use quote::{quote, ToTokens};
 use syn::{
-    parse::{Parse, ParseStream}, parse_macro_input, Fields,
-    FieldsNamed, Ident, ItemStruct, Token, ParenthesizedGenericArguments,
+    parenthesized, parse::{Parse, ParseStream}, parse_macro_input, punctuated::Punctuated, Fields,
+    Expr, FieldsNamed, Ident, ItemStruct, LitStr, Token,
 };
 
-const ATTRS_LEN: usize = 47;
-const HTML_ELEMENT_ATTRS: [&str; ATTRS_LEN] = [
+// Attributes every #[html_element] gets when no attribute groups are selected.
+const DEFAULT_ATTRS: [&str; 47] = [
     "id",
     "class",
     "onclick",
@@ -61,15 +61,174 @@ const HTML_ELEMENT_ATTRS: [&str; ATTRS_LEN] = [
     "hx-ws",
 ];
 
+// https://developer.mozilla.org/en-US/docs/Web/HTML/Global_attributes
+const GLOBAL_ATTRS: [&str; 10] = [
+    "id",
+    "class",
+    "style",
+    "title",
+    "role",
+    "tabindex",
+    "hidden",
+    "lang",
+    "dir",
+    "onclick",
+];
+
+// https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes
+const ARIA_ATTRS: [&str; 16] = [
+    "aria-label",
+    "aria-labelledby",
+    "aria-describedby",
+    "aria-expanded",
+    "aria-hidden",
+    "aria-controls",
+    "aria-current",
+    "aria-haspopup",
+    "aria-orientation",
+    "aria-live",
+    "aria-modal",
+    "aria-selected",
+    "aria-disabled",
+    "aria-invalid",
+    "aria-required",
+    "aria-pressed",
+];
+
+const FORM_ATTRS: [&str; 19] = [
+    "name",
+    "value",
+    "placeholder",
+    "autocomplete",
+    "type",
+    "for",
+    "form",
+    "disabled",
+    "required",
+    "readonly",
+    "checked",
+    "multiple",
+    "min",
+    "max",
+    "step",
+    "pattern",
+    "accept",
+    "minlength",
+    "maxlength",
+];
+
+const LINK_ATTRS: [&str; 5] = ["href", "target", "rel", "download", "hreflang"];
+
+// `DEFAULT_ATTRS` minus the non-htmx attributes at the top.
+const HTMX_ATTRS_START: usize = 11;
+
+const ATTR_GROUPS: [&str; 5] = ["global", "aria", "form", "link", "htmx"];
+
+fn attr_group(group: &str) -> Option<Vec<&'static str>> {
+    match group {
+        "global" => Some(GLOBAL_ATTRS.to_vec()),
+        "aria" => Some(ARIA_ATTRS.to_vec()),
+        "form" => Some(FORM_ATTRS.to_vec()),
+        "link" => Some(LINK_ATTRS.to_vec()),
+        "htmx" => Some(DEFAULT_ATTRS[HTMX_ATTRS_START..].to_vec()),
+        _ => None,
+    }
+}
+
+// Every attribute name that belongs to a group. Used to validate idents passed to spread_attrs!
+fn known_attrs() -> Vec<&'static str> {
+    let mut attrs = DEFAULT_ATTRS.to_vec();
+    for group in ATTR_GROUPS {
+        for attr in attr_group(group).unwrap_or_default() {
+            if !attrs.contains(&attr) {
+                attrs.push(attr);
+            }
+        }
+    }
+    attrs
+}
+
+/**
+ * Arguments to #[html_element(...)]
+ *
+ * A list of attribute groups (`global`, `aria`, `form`, `link`, `htmx`) and/or extra attribute
+ * names as string literals (ex: `"data-testid"`, `"hx-on:click"`).
+ * With no arguments, the element gets `DEFAULT_ATTRS`.
+ */
+struct HtmlElementArgs {
+    attrs: Vec<String>,
+}
+
+impl Parse for HtmlElementArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.is_empty() {
+            return Ok(HtmlElementArgs {
+                attrs: DEFAULT_ATTRS.iter().map(|attr| attr.to_string()).collect(),
+            });
+        }
+
+        let mut attrs: Vec<String> = Vec::new();
+        let mut push = |attr: String| {
+            if !attrs.contains(&attr) {
+                attrs.push(attr);
+            }
+        };
+
+        let args = Punctuated::<HtmlElementArg, Token![,]>::parse_terminated(input)?;
+        for arg in args {
+            match arg {
+                HtmlElementArg::Group(group) => {
+                    let group_attrs = attr_group(&group.to_string()).ok_or_else(|| {
+                        syn::Error::new(
+                            group.span(),
+                            format!(
+                                "Unknown attribute group `{}`.{} Valid groups: {}. Use a string literal for a single attribute.",
+                                group,
+                                did_you_mean(&group.to_string(), &ATTR_GROUPS),
+                                ATTR_GROUPS.map(|g| format!("`{}`", g)).join(", "),
+                            ),
+                        )
+                    })?;
+                    group_attrs.into_iter().for_each(|attr| push(attr.to_string()));
+                }
+                HtmlElementArg::Attr(attr) => {
+                    push(validate_attr_name(&attr)?);
+                }
+            }
+        }
+
+        Ok(HtmlElementArgs { attrs })
+    }
+}
+
+enum HtmlElementArg {
+    Group(Ident),
+    Attr(LitStr),
+}
+
+impl Parse for HtmlElementArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(LitStr) {
+            Ok(HtmlElementArg::Attr(input.parse()?))
+        } else {
+            Ok(HtmlElementArg::Group(input.parse()?))
+        }
+    }
+}
+
 #[proc_macro_attribute]
-pub fn html_element(_: TokenStream, input: TokenStream) -> TokenStream {
-    let html_element = parse_macro_input!(input as HtmlElementStruct);
+pub fn html_element(args: TokenStream, input: TokenStream) -> TokenStream {
+    let HtmlElementArgs { attrs } = parse_macro_input!(args as HtmlElementArgs);
+    let mut html_element = parse_macro_input!(input as HtmlElementStruct);
+    html_element.attrs = attrs;
+
     quote! { #html_element }.to_token_stream().into()
 }
 
 struct HtmlElementStruct {
     name: Ident,
     item: ItemStruct,
+    attrs: Vec<String>,
 }
 
 impl Parse for HtmlElementStruct {
@@ -77,7 +236,23 @@ impl Parse for HtmlElementStruct {
         let item = input.parse::<ItemStruct>()?;
         let name = item.ident.clone();
 
-        Ok(HtmlElementStruct { name, item })
+        match &item.fields {
+            Fields::Named(_) => (),
+            Fields::Unnamed(fields) => return Err(syn::Error::new_spanned(
+                fields,
+                "#[html_element] only supports structs with named fields.",
+            )),
+            Fields::Unit => return Err(syn::Error::new(
+                name.span(),
+                "#[html_element] only supports structs with named fields.",
+            )),
+        }
+
+        Ok(HtmlElementStruct {
+            name,
+            item,
+            attrs: vec![],
+        })
     }
 }
 
@@ -89,10 +264,33 @@ impl ToTokens for HtmlElementStruct {
 
         let original_fields = match item.fields {
             Fields::Named(named) => named.named,
-            _ => panic!("not named fields"),
+            _ => unreachable!("checked when parsing"),
         };
 
-        let attr_idents = create_attr_idents();
+        // Fields declared on the struct take precedence over generated attribute fields.
+        let declared: Vec<String> = original_fields
+            .iter()
+            .filter_map(|field| field.ident.as_ref().map(|ident| ident.to_string()))
+            .collect();
+        let attr_keys: Vec<&String> = self
+            .attrs
+            .iter()
+            .filter(|attr| !declared.contains(&attr_ident(attr).to_string()))
+            .collect();
+        let attr_idents: Vec<Ident> = attr_keys.iter().map(|attr| attr_ident(attr)).collect();
+
+        // Typed htmx attributes (see web_client::server::hx::Hx), unless the struct has its own `hx`.
+        let (hx_field, hx) = if declared.contains(&"hx".to_string()) {
+            (quote! {}, quote! { ::web_client::server::hx::Hx::default() })
+        } else {
+            (
+                quote! {
+                    #[builder(setter(into), default)]
+                    hx: ::web_client::server::hx::Hx,
+                },
+                quote! { hx },
+            )
+        };
 
         let fields = quote! {
             {
@@ -103,6 +301,15 @@ impl ToTokens for HtmlElementStruct {
                     #attr_idents: String,
                 )*
 
+                #hx_field
+
+                // Snapshot of the attribute fields, so spread_attrs! doesn't need to borrow all of props.
+                #[builder(
+                    default = web_client::html_attr_fields(vec![#((#attr_keys, #attr_idents.clone())),*], &#hx),
+                    setter(skip)
+                )]
+                html_attrs: Vec<(String, String)>,
+
                 #[builder(default)]
                 attrs: ::web_client::server::attrs::Attrs,
             }
@@ -121,28 +328,10 @@ impl ToTokens for HtmlElementStruct {
             #item
         });
 
-        let attr_keys = HTML_ELEMENT_ATTRS;
         tokens.extend(quote! {
             impl #name {
-                fn html_attrs_to_hashmap(&self) -> std::collections::HashMap<&'static str, String> {
-                    let mut map = std::collections::HashMap::new();
-
-                    #(
-                        map.insert(#attr_keys, web_client::concat_attribute(&self.#attr_idents, self.attrs.get(#attr_keys)));
-                    )*
-
-                    // Check for special case html attributes that are not part of HtmlElementProps
-                    if let Some(for_input) = self.attrs.get("for") {
-                        map.insert("for", for_input.to_string());
-                    }
-                    if let Some(for_input) = self.attrs.get("type") {
-                        map.insert("type", for_input.to_string());
-                    }          
-
-                    let attrs = vec![#(#attr_keys),*];
-                    map.extend(self.attrs.to_hashmap_excluding(attrs));
-
-                    map
+                fn html_attrs_to_hashmap(&self) -> std::collections::HashMap<String, String> {
+                    web_client::html_attrs_to_hashmap(&self.html_attrs, &self.attrs)
                 }
             }
 
@@ -155,41 +344,81 @@ impl ToTokens for HtmlElementStruct {
     }
 }
 
-fn create_attr_idents() -> [Ident; ATTRS_LEN] {
-    HTML_ELEMENT_ATTRS.map(|attr| {
-        let attr = attr.replace('-', "_");
-        let attr = attr.as_str();
+// `aria-label` => `aria_label`, `hx-on:click` => `hx_on_click`, `type` => `type_`
+fn attr_ident(attr: &str) -> Ident {
+    Ident::new(&attr_ident_name(attr), Span::call_site())
+}
 
-        Ident::new(attr, Span::call_site())
-    })
+fn attr_ident_name(attr: &str) -> String {
+    let ident = attr.replace(['-', ':'], "_");
+    match ident.as_str() {
+        "type" | "for" | "async" | "loop" | "as" => format!("{}_", ident),
+        _ => ident,
+    }
 }
 
-// Right now we only support omit transform in spread_attr!
+const TRANSFORMERS: [&str; 4] = ["omit", "pick", "rename", "default"];
+
+// Pipe transformers, applied in order: `spread_attrs!(props | omit(class) | default(id = "foo"))`
 enum Transformer {
-    Omit(ParenthesizedGenericArguments),
+    Omit(Vec<String>),
+    Pick(Vec<String>),
+    Rename(Vec<(String, String)>),
+    Default(Vec<(String, Expr)>),
 }
 
-type TransformerFn = (Ident, ParenthesizedGenericArguments);
+impl Parse for Transformer {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let fn_name: Ident = input.parse()?;
+        let name = fn_name.to_string();
+
+        if !TRANSFORMERS.contains(&name.as_str()) {
+            return Err(syn::Error::new(
+                fn_name.span(),
+                format!(
+                    "Unrecognized pipe transformer `{}`.{} Valid pipe transformers: {}",
+                    name,
+                    did_you_mean(&name, &TRANSFORMERS),
+                    TRANSFORMERS.map(|t| format!("`{}`", t)).join(", "),
+                ),
+            ));
+        }
+
+        let content;
+        parenthesized!(content in input);
+
+        match name.as_str() {
+            "omit" => Ok(Transformer::Omit(parse_attr_names(&content)?)),
+            "pick" => Ok(Transformer::Pick(parse_attr_names(&content)?)),
+            "rename" => Ok(Transformer::Rename(parse_separated(&content, |input| {
+                let from = parse_attr_name(input)?;
+                input.parse::<Token![=>]>()?;
+                Ok((from, parse_attr_name(input)?))
+            })?)),
+            "default" => Ok(Transformer::Default(parse_separated(&content, |input| {
+                let attr = parse_attr_name(input)?;
+                input.parse::<Token![=]>()?;
+                Ok((attr, input.parse::<Expr>()?))
+            })?)),
+            _ => unreachable!(),
+        }
+    }
+}
 
-#[derive(Debug)]
 struct AttrsSpread {
     props: Ident,
-    transforms: Vec<TransformerFn>,
+    transforms: Vec<Transformer>,
 }
 
 impl Parse for AttrsSpread {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let mut transforms: Vec<TransformerFn> = Vec::new();
+        let mut transforms: Vec<Transformer> = Vec::new();
 
         let props: Ident = input.parse()?;
-    
-        while !input.is_empty() { 
-            input.parse::<Token![|]>()?;
 
-            let fn_name: Ident = input.parse()?;    
-            let fn_args: ParenthesizedGenericArguments = input.parse()?;
-
-            transforms.push((fn_name, fn_args));
+        while !input.is_empty() {
+            input.parse::<Token![|]>()?;
+            transforms.push(input.parse()?);
         }
 
         Ok(AttrsSpread { props, transforms })
@@ -201,54 +430,120 @@ pub fn spread_attrs(input: TokenStream) -> TokenStream {
     let ast = syn::parse_macro_input!(input as AttrsSpread);
     let AttrsSpread { props, transforms } = ast;
 
-    let mut attr_keys = HTML_ELEMENT_ATTRS.clone().to_vec();
-    let mut attr_idents = create_attr_idents().to_vec();
+    let transforms = transforms.iter().map(|transform| match transform {
+        Transformer::Omit(attrs) => quote! { .omit(&[#(#attrs),*]) },
+        Transformer::Pick(attrs) => quote! { .pick(&[#(#attrs),*]) },
+        Transformer::Rename(renames) => {
+            let (from, to): (Vec<&String>, Vec<&String>) = renames.iter().map(|(from, to)| (from, to)).unzip();
+            quote! { #(.rename(#from, #to))* }
+        }
+        Transformer::Default(defaults) => {
+            let (attrs, values): (Vec<&String>, Vec<&Expr>) = defaults.iter().map(|(attr, value)| (attr, value)).unzip();
+            quote! { #(.with_default(#attrs, #values))* }
+        }
+    });
 
-    for (fn_name, fn_args) in transforms {
-        let transform = match fn_name.to_string().as_str()  {
-            "omit" => Transformer::Omit(fn_args),
-            _ => panic!("Unrecognized pipe transfomer: `{}`. Valid pipe transformers: `omit`", fn_name),
-        };
+    let gen = quote! {
+        ::web_client::server::attrs::Attrs::from(
+            ::web_client::server::attrs::SpreadAttrs::new(&#props.html_attrs, &#props.attrs)
+                #(#transforms)*
+        )
+    };
 
-        transform_attrs(transform, &mut attr_keys, &mut attr_idents);
-    }
+    gen.into()
+}
 
-    let gen = quote! {
-        {
-            let mut map = std::collections::HashMap::new();
-            #(
-                map.insert(#attr_keys, web_client::concat_attribute(&#props.#attr_idents, #props.attrs.get(#attr_keys)));
-            )*
+// Attribute names passed to a transformer: idents of known attributes (`hx_get`) or string literals (`"data-id"`).
+fn parse_attr_names(input: ParseStream) -> syn::Result<Vec<String>> {
+    parse_separated(input, parse_attr_name)
+}
+
+fn parse_separated<T>(input: ParseStream, parse: fn(ParseStream) -> syn::Result<T>) -> syn::Result<Vec<T>> {
+    let mut items = vec![];
 
-            let attrs = vec![#(#attr_keys),*];
-            map.extend(#props.attrs.to_hashmap_excluding(attrs));
+    while !input.is_empty() {
+        items.push(parse(input)?);
 
-            ::web_client::server::attrs::Attrs::from(map)
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
         }
-    };
+    }
 
-    gen.into()
+    Ok(items)
 }
 
-fn transform_attrs(transform: Transformer, attr_keys: &mut Vec<&str>, attr_idents: &mut Vec<Ident>) {
-    match transform {
-        Transformer::Omit(args) => {
-            for arg in args.inputs {
-                match arg {
-                    syn::Type::Path(path) => {
-                        let ident = path.path.get_ident().unwrap();
-                        if !HTML_ELEMENT_ATTRS.contains(&ident.to_string().replace('_', "-").as_str()) {
-                            panic!(
-                                "Cannot omit field {}. It doesn't exit in HtmlElementProps.",
-                                ident,
-                            );
-                        }
-                        attr_keys.retain(|f| f != &ident.to_string());
-                        attr_idents.retain(|f| ident != &f.to_string());
-                    },
-                    _ => panic!("Expected a path"),
-                }
-            }
-        },
+fn parse_attr_name(input: ParseStream) -> syn::Result<String> {
+    if input.peek(LitStr) {
+        return validate_attr_name(&input.parse::<LitStr>()?);
+    }
+
+    if !input.peek(Ident) {
+        return Err(input.error("Expected an attribute name (ex: `class`, `hx_get` or `\"data-id\"`)."));
+    }
+
+    let ident: Ident = input.parse()?;
+    let name = ident.to_string();
+    let known_attrs = known_attrs();
+    let known_idents: Vec<String> = known_attrs.iter().map(|attr| attr_ident(attr).to_string()).collect();
+
+    match known_idents.iter().position(|known| known == &name) {
+        Some(index) => Ok(known_attrs[index].to_string()),
+        None => Err(syn::Error::new(
+            ident.span(),
+            format!(
+                "Unknown html element attribute `{}`.{} Use a string literal (ex: `\"data-id\"`) for other attributes.",
+                name,
+                did_you_mean(&name, &known_idents),
+            ),
+        )),
     }
 }
+
+fn validate_attr_name(attr: &LitStr) -> syn::Result<String> {
+    let name = attr.value();
+    if name.is_empty() || name.contains(|c: char| c.is_whitespace() || c.is_control() || "\"'<>/=".contains(c)) {
+        return Err(syn::Error::new(
+            attr.span(),
+            format!("`{}` is not a valid html attribute name.", name),
+        ));
+    }
+
+    // Each attribute becomes a props field, `Ident::new` would panic without a span.
+    let ident = attr_ident_name(&name);
+    if syn::parse_str::<Ident>(&ident).is_err() {
+        return Err(syn::Error::new(
+            attr.span(),
+            format!("`{}` can't be a props field name (`{}` isn't a valid identifier). Start it with a letter and only use letters, digits, `_`, `-` and `:`.", name, ident),
+        ));
+    }
+
+    Ok(name)
+}
+
+// " Did you mean `x`?" when a candidate is close enough to be a typo, otherwise an empty string.
+fn did_you_mean<S: AsRef<str>>(name: &str, candidates: &[S]) -> String {
+    candidates
+        .iter()
+        .map(|candidate| (levenshtein(name, candidate.as_ref()), candidate.as_ref()))
+        .filter(|(distance, candidate)| *distance <= std::cmp::max(2, candidate.len() / 3))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| format!(" Did you mean `{}`?", candidate))
+        .unwrap_or_default()
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous + usize::from(ca != *cb);
+            previous = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(previous + 1);
+        }
+    }
+
+    row[b.len()]
+}
@@ -0,0 +1,8 @@
+use web_macros::html_element;
+
+#[html_element(global, "x-on:click.prevent")]
+struct LinkProps {
+    children: String,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use web_macros::html_element;
+
+#[html_element(global, "1st")]
+struct LinkProps {
+    children: String,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use web_macros::html_element;
+
+#[html_element(global, "data id")]
+struct LinkProps {
+    children: String,
+}
+
+fn main() {}
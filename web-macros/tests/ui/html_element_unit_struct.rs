@@ -0,0 +1,6 @@
+use web_macros::html_element;
+
+#[html_element]
+struct UnitProps;
+
+fn main() {}
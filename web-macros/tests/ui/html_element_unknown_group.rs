@@ -0,0 +1,8 @@
+use web_macros::html_element;
+
+#[html_element(global, arai)]
+struct LinkProps {
+    children: String,
+}
+
+fn main() {}
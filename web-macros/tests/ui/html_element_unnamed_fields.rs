@@ -0,0 +1,6 @@
+use web_macros::html_element;
+
+#[html_element]
+struct TupleProps(String);
+
+fn main() {}
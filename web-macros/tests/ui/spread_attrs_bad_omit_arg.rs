@@ -0,0 +1,6 @@
+use web_macros::spread_attrs;
+
+fn main() {
+    let props = ();
+    spread_attrs!(props | omit(id, 42));
+}